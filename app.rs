@@ -1,38 +1,187 @@
-use crate::state::AppState; // Corrected typo here
+use crate::state::{AppState, BendInputState, PendingJobAction}; // Corrected typo here
 use crate::ui;
 use crate::logic;
+use crate::message_log::LogLevel;
+use crate::i18n::trf;
 use eframe::{egui, App, CreationContext};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use vouwbank::db;
+use vouwbank::model::MaterialName;
+
+// Window geometry and panel widths are persisted by eframe itself
+const RECENT_FILES_KEY: &str = "recent_files";
+const JOB_LIBRARY_DIR_KEY: &str = "job_library_dir";
+const LANGUAGE_KEY: &str = "language";
+const LENGTH_UNIT_KEY: &str = "length_unit";
+const BEND_INPUT_KEY: &str = "bend_input";
+const SELECTED_TOOLS_KEY: &str = "selected_tools"; // (punch name, die name)
+const OPEN_JOBS_KEY: &str = "open_jobs";
+const ACTIVE_TAB_KEY: &str = "active_tab";
+const PRODUCTION_QUEUE_KEY: &str = "production_queue"; // Orders and the parts bent history
+const QUOTE_RATES_KEY: &str = "quote_rates";
+const MATERIAL_PRICES_KEY: &str = "material_prices"; // (material, price per kg)
+const MACHINE_SPEEDS_KEY: &str = "machine_speeds";
+
+/// A job that was open on exit, unsaved changes included. Jobs whose changes
+/// were discarded in the exit prompt are closed before the session is saved.
+#[derive(Serialize, Deserialize)]
+struct OpenJob {
+    json: String, // Versioned job file contents, see db::job_to_json
+    file: Option<PathBuf>,
+    dirty: bool,
+}
+
+/// The bend inputs as typed. Ranges and quantities are not saved; they come
+/// from `BendInputState::default()` so a newer build's limits apply.
+#[derive(Serialize, Deserialize)]
+struct SavedBendInput {
+    position: String,
+    target_angle: String,
+    radius: String,
+    joggle_offset: String,
+    selected_direction_idx: usize,
+    selected_method_idx: usize,
+    selected_feature_idx: usize,
+}
+
+impl SavedBendInput {
+    fn new(input: &BendInputState) -> Self {
+        SavedBendInput {
+            position: input.position.text.clone(),
+            target_angle: input.target_angle.text.clone(),
+            radius: input.radius.text.clone(),
+            joggle_offset: input.joggle_offset.text.clone(),
+            selected_direction_idx: input.selected_direction_idx,
+            selected_method_idx: input.selected_method_idx,
+            selected_feature_idx: input.selected_feature_idx,
+        }
+    }
+
+    fn into_input(self) -> BendInputState {
+        let mut input = BendInputState::default();
+        input.position.text = self.position;
+        input.target_angle.text = self.target_angle;
+        input.radius.text = self.radius;
+        input.joggle_offset.text = self.joggle_offset;
+        input.selected_direction_idx = self.selected_direction_idx;
+        input.selected_method_idx = self.selected_method_idx;
+        input.selected_feature_idx = self.selected_feature_idx;
+        input
+    }
+}
 
 pub struct MyApp {
     state: AppState,
-    initialized: bool,
 }
 
 impl MyApp {
-    pub fn new(_cc: &CreationContext<'_>) -> Self {
-
+    pub fn new(cc: &CreationContext<'_>) -> Self {
+        let mut state = AppState::default(); // Initializes with CNC defaults
+        logic::perform_initial_setup(&cc.egui_ctx, &mut state);
+        if let Some(storage) = cc.storage {
+            restore_session(storage, &mut state);
+        }
+        Self { state }
+    }
+}
 
-        // Set the visual style to light mode upon creation
-        Self {
-            
-            state: AppState::default(), // Initializes with CNC defaults
-            initialized: false,
+/// Restores the settings and the last job on top of the defaults. Anything
+/// missing or unreadable keeps its default.
+fn restore_session(storage: &dyn eframe::Storage, state: &mut AppState) {
+    if let Some(recent_files) = eframe::get_value(storage, RECENT_FILES_KEY) {
+        state.recent_files = recent_files;
+    }
+    if let Some(dir) = eframe::get_value(storage, JOB_LIBRARY_DIR_KEY) {
+        state.job_library.directory_str = dir;
+    }
+    if let Some(queue) = eframe::get_value(storage, PRODUCTION_QUEUE_KEY) {
+        state.production.queue = queue;
+    }
+    if let Some(rates) = eframe::get_value(storage, QUOTE_RATES_KEY) {
+        state.quote.rates = rates;
+    }
+    if let Some(speeds) = eframe::get_value(storage, MACHINE_SPEEDS_KEY) {
+        state.machine_speeds = speeds;
+    }
+    for (name, price) in eframe::get_value::<Vec<(MaterialName, f64)>>(storage, MATERIAL_PRICES_KEY).unwrap_or_default() {
+        if let Some(material) = state.available_materials.get_mut(&name) {
+            material.price_per_kg = price;
+        }
+    }
+    if let Some(language) = eframe::get_value(storage, LANGUAGE_KEY) {
+        state.language = language;
+    }
+    if let Some(unit) = eframe::get_value(storage, LENGTH_UNIT_KEY) {
+        logic::set_length_unit(state, unit);
+    }
+    if let Some(saved) = eframe::get_value::<SavedBendInput>(storage, BEND_INPUT_KEY) {
+        logic::restore_bend_input(state, saved.into_input());
+    }
+    if let Some((punch, die)) = eframe::get_value::<(String, String)>(storage, SELECTED_TOOLS_KEY) {
+        logic::select_tools_by_name(state, &punch, &die);
+    }
+    for open_job in eframe::get_value::<Vec<OpenJob>>(storage, OPEN_JOBS_KEY).unwrap_or_default() {
+        match db::job_from_json(&open_job.json) {
+            Ok(job) => logic::restore_job(state, job, open_job.file, open_job.dirty),
+            Err(e) => {
+                let message = trf(state.language, "session.restore_failed", &[&e]);
+                state.log(LogLevel::Warning, message);
+            }
         }
     }
+    if let Some(active_tab) = eframe::get_value(storage, ACTIVE_TAB_KEY) {
+        logic::switch_tab(state, active_tab);
+    }
 }
 
 impl App for MyApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, RECENT_FILES_KEY, &self.state.recent_files);
+        eframe::set_value(storage, JOB_LIBRARY_DIR_KEY, &self.state.job_library.directory_str);
+        eframe::set_value(storage, PRODUCTION_QUEUE_KEY, &self.state.production.queue);
+        eframe::set_value(storage, QUOTE_RATES_KEY, &self.state.quote.rates);
+        eframe::set_value(storage, MACHINE_SPEEDS_KEY, &self.state.machine_speeds);
+        let prices: Vec<(MaterialName, f64)> = self.state.available_materials.values().map(|m| (m.name.clone(), m.price_per_kg)).collect();
+        eframe::set_value(storage, MATERIAL_PRICES_KEY, &prices);
+        eframe::set_value(storage, LANGUAGE_KEY, &self.state.language);
+        eframe::set_value(storage, LENGTH_UNIT_KEY, &self.state.length_unit);
+        eframe::set_value(storage, BEND_INPUT_KEY, &SavedBendInput::new(&self.state.bend_input));
+        let state = &self.state;
+        if let (Some(punch), Some(die)) = (
+            state.available_punches.get(state.tooling_input.selected_punch_idx),
+            state.available_dies.get(state.tooling_input.selected_die_idx),
+        ) {
+            eframe::set_value(storage, SELECTED_TOOLS_KEY, &(punch.name.clone(), die.name.clone()));
+        }
+        let open_jobs: Vec<OpenJob> = state.tabs.iter()
+            .filter_map(|tab| {
+                let json = db::job_to_json(&tab.job).ok()?;
+                Some(OpenJob { json, file: tab.file.clone(), dirty: tab.dirty })
+            })
+            .collect();
+        eframe::set_value(storage, OPEN_JOBS_KEY, &open_jobs);
+        eframe::set_value(storage, ACTIVE_TAB_KEY, &state.active_tab);
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        if !self.initialized {
-            logic::perform_initial_setup(ctx, &mut self.state);
-            self.initialized = true;
+        // Hold the window open while a job has unsaved changes
+        if ctx.input(|i| i.viewport().close_requested()) && self.state.tabs.iter().any(|tab| tab.dirty) && !self.state.close_confirmed {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            logic::request_job_action(ctx, &mut self.state, PendingJobAction::Exit);
+        }
+
+        let title = logic::window_title(&self.state);
+        if title != self.state.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.state.window_title = title;
         }
 
         ui::draw_main_ui(ctx, &mut self.state);
 
         // Repaint if simulation or profile loading is in a "pending" state
-        let sim_status_lower = self.state.simulation_status.to_lowercase();
-        let profile_status_lower = self.state.profile_load_status.to_lowercase();
+        let sim_status_lower = self.state.tab().simulation_status.to_lowercase();
+        let profile_status_lower = self.state.tab().profile_load_status.to_lowercase();
 
         if sim_status_lower.contains("simulating") ||
            sim_status_lower.contains("processing") ||
@@ -0,0 +1,46 @@
+// src/assets.rs
+// Images the GUI needs, compiled into the binary so the app works from any
+// working directory (desktop shortcut, installed binary). A directory named in
+// VOUWBANK_ASSET_DIR can override single files, e.g. a customer logo.
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+pub const ASSET_DIR_ENV: &str = "VOUWBANK_ASSET_DIR";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Asset {
+    Logo,
+}
+
+impl Asset {
+    /// Name of the override file in the asset directory.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Asset::Logo => "logo.png",
+        }
+    }
+
+    pub fn embedded(self) -> &'static [u8] {
+        match self {
+            Asset::Logo => include_bytes!("Vouwbank-Simulator-CNC.png"),
+        }
+    }
+}
+
+/// The override directory, if configured.
+pub fn override_dir() -> Option<PathBuf> {
+    std::env::var_os(ASSET_DIR_ENV).filter(|dir| !dir.is_empty()).map(PathBuf::from)
+}
+
+/// Path of the override file for `asset`, if one exists.
+pub fn override_path(asset: Asset) -> Option<PathBuf> {
+    override_dir().map(|dir| dir.join(asset.file_name())).filter(|path| path.is_file())
+}
+
+/// The override file's contents when there is one, the embedded copy otherwise.
+pub fn load(asset: Asset) -> Result<Cow<'static, [u8]>, std::io::Error> {
+    match override_path(asset) {
+        Some(path) => std::fs::read(path).map(Cow::Owned),
+        None => Ok(Cow::Borrowed(asset.embedded())),
+    }
+}
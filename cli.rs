@@ -0,0 +1,178 @@
+// src/cli.rs
+// Batch front end: validates and simulates job files without opening a window.
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use vouwbank::cycle_time::MachineSpeeds;
+use vouwbank::model::{self, Die, Job, Punch};
+use vouwbank::units::LengthUnit;
+use vouwbank::{db, export, simulation, tooling};
+
+const EXIT_VALIDATION_FAILED: u8 = 1;
+const EXIT_USAGE_OR_IO: u8 = 2;
+const DEFAULT_PNG_SIZE_PX: u32 = 800;
+
+const USAGE: &str = "\
+Usage: vouwbank-cli [OPTIONS] <JOB.json>...
+
+Validates and simulates each job and writes <job>.report.txt, <job>.svg,
+<job>.png and <job>.nc (machine program) to the output directory.
+
+Options:
+  -o, --out-dir <DIR>   Output directory (default: current directory)
+      --punch <NAME>    Punch to use (default: recommended for the job)
+      --die <NAME>      Die to use (default: recommended for the job)
+      --png-size <PX>   Width and height of the profile PNG (default: 800)
+      --no-png          Skip the PNG export
+      --units <UNIT>    Lengths in the report and program: mm or inch (default: mm)
+  -h, --help            Show this help
+
+Exit codes: 0 all jobs valid, 1 a job failed validation, 2 usage or I/O error.
+All jobs are processed; the highest exit code of any job is returned.";
+
+struct Options {
+    out_dir: PathBuf,
+    punch: Option<String>,
+    die: Option<String>,
+    png_size_px: Option<u32>,
+    unit: LengthUnit,
+    jobs: Vec<PathBuf>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        out_dir: PathBuf::from("."),
+        punch: None,
+        die: None,
+        png_size_px: Some(DEFAULT_PNG_SIZE_PX),
+        unit: LengthUnit::Millimeter,
+        jobs: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "-o" | "--out-dir" => options.out_dir = PathBuf::from(value(&arg)?),
+            "--punch" => options.punch = Some(value(&arg)?),
+            "--die" => options.die = Some(value(&arg)?),
+            "--png-size" => {
+                let raw = value(&arg)?;
+                let size = raw.parse::<u32>().ok().filter(|s| *s > 0).ok_or_else(|| format!("Invalid PNG size: {}", raw))?;
+                options.png_size_px = Some(size);
+            }
+            "--no-png" => options.png_size_px = None,
+            "--units" => {
+                let raw = value(&arg)?;
+                options.unit = LengthUnit::from_name(&raw).ok_or_else(|| format!("Invalid unit: {} (use mm or inch)", raw))?;
+            }
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with('-') => return Err(format!("Unknown option: {}", arg)),
+            _ => options.jobs.push(PathBuf::from(arg)),
+        }
+    }
+    if options.jobs.is_empty() {
+        return Err("No job files given.".to_string());
+    }
+    Ok(options)
+}
+
+/// Tools named on the command line, or the best recommendation for the tightest
+/// radius and angle in the job.
+fn select_tooling<'a>(
+    options: &Options,
+    job: &Job,
+    material: &model::MaterialDetails,
+    punches: &'a [Punch],
+    dies: &'a [Die],
+) -> Result<(&'a Punch, &'a Die), String> {
+    let radius_mm = job.steps.iter().map(|s| s.radius_mm).fold(f64::INFINITY, f64::min);
+    let angle_deg = job.steps.iter().map(|s| s.target_angle_deg).fold(f64::INFINITY, f64::min);
+    let recommended = tooling::recommend_tooling(job, material, punches, dies, radius_mm, angle_deg).into_iter().next();
+
+    let punch = match &options.punch {
+        Some(name) => punches.iter().find(|p| p.name == *name).ok_or_else(|| format!("Unknown punch: {}", name))?,
+        None => recommended.as_ref().and_then(|r| punches.get(r.punch_idx)).ok_or("No punches available.")?,
+    };
+    let die = match &options.die {
+        Some(name) => dies.iter().find(|d| d.name == *name).ok_or_else(|| format!("Unknown die: {}", name))?,
+        None => recommended.as_ref().and_then(|r| dies.get(r.die_idx)).ok_or("No dies available.")?,
+    };
+    Ok((punch, die))
+}
+
+enum JobOutcome {
+    Valid,
+    Invalid,
+}
+
+fn write_output(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|e| format!("Failed to write '{}': {}", path.display(), e))?;
+    println!("  wrote {}", path.display());
+    Ok(())
+}
+
+fn process_job(options: &Options, job_path: &Path) -> Result<JobOutcome, String> {
+    let job = db::load_job_from_file(job_path).map_err(|e| format!("Failed to load job '{}': {}", job_path.display(), e))?;
+    let materials = model::default_materials();
+    let material = materials.get(&job.sheet.material_name)
+        .ok_or_else(|| format!("Unknown material: {}", job.sheet.material_name))?;
+    let punches = model::default_punches();
+    let dies = model::default_dies();
+    let (punch, die) = select_tooling(options, &job, material, &punches, &dies)?;
+    let setup = model::MachineSetup { sheet: &job.sheet, material, punch, die };
+
+    println!("{}: '{}' with {} / {}", job_path.display(), job.name, punch.name, die.name);
+    let result = match simulation::simulate(&setup, &job) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("  validation failed: {}", e);
+            return Ok(JobOutcome::Invalid);
+        }
+    };
+    for warning in &result.warnings {
+        println!("  {}", warning);
+    }
+
+    let stem = job_path.file_stem().map_or_else(|| "job".into(), |s| s.to_string_lossy());
+    let out = |extension: &str| options.out_dir.join(format!("{}.{}", stem, extension));
+    write_output(&out("report.txt"), export::simulation_report(&setup, &job, &result, &MachineSpeeds::default(), options.unit))?;
+    write_output(&out("svg"), export::profile_svg(&result.profile, job.sheet.thickness_mm))?;
+    write_output(&out("nc"), export::machine_program(&setup, &job, &result, options.unit))?;
+    if let Some(size) = options.png_size_px {
+        let path = out("png");
+        export::profile_image(&result.profile, size, size).save(&path)
+            .map_err(|e| format!("Failed to write '{}': {}", path.display(), e))?;
+        println!("  wrote {}", path.display());
+    }
+    Ok(JobOutcome::Valid)
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) if message.is_empty() => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return ExitCode::from(EXIT_USAGE_OR_IO);
+        }
+    };
+    if let Err(e) = std::fs::create_dir_all(&options.out_dir) {
+        eprintln!("Failed to create '{}': {}", options.out_dir.display(), e);
+        return ExitCode::from(EXIT_USAGE_OR_IO);
+    }
+
+    // Every job is processed; the exit code is that of the worst job.
+    let mut exit_code = 0;
+    for job_path in &options.jobs {
+        match process_job(&options, job_path) {
+            Ok(JobOutcome::Valid) => {}
+            Ok(JobOutcome::Invalid) => exit_code = exit_code.max(EXIT_VALIDATION_FAILED),
+            Err(message) => {
+                eprintln!("{}", message);
+                exit_code = exit_code.max(EXIT_USAGE_OR_IO);
+            }
+        }
+    }
+    ExitCode::from(exit_code)
+}
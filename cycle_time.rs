@@ -0,0 +1,229 @@
+// src/cycle_time.rs
+// Cycle time estimate for one part: ram strokes per hit, backgauge travel
+// between hits, tool changes and the operator handling the part.
+use crate::geometry;
+use crate::model::{BendMethod, BendStep, Job, MachineSetup};
+use serde::{Deserialize, Serialize};
+
+const MAX_HALF_DEFLECTION_DEG: f64 = 80.0; // Caps the stroke for very acute hits (hem pre-bends)
+
+/// Axis speeds and handling times of the press brake. The defaults are typical
+/// for a mid-size hydraulic machine with one operator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MachineSpeeds {
+    pub approach_mm_s: f64,     // Ram rapid down to the sheet
+    pub bending_mm_s: f64,      // Ram while forming
+    pub return_mm_s: f64,       // Ram rapid up
+    pub opening_mm: f64,        // Gap between punch tip and sheet before each hit
+    pub dwell_s: f64,           // Pressure hold and decompression at the bottom
+    pub backgauge_mm_s: f64,    // X axis
+    pub backgauge_settle_s: f64, // After each move
+    pub tool_change_s: f64,     // Moving to the flattening tools and back
+    pub flip_s: f64,            // Turning the part over (bend direction changes)
+    pub rotate_s: f64,          // Turning the part end for end (gauged end changes)
+    pub load_unload_s: f64,     // Taking the blank and stacking the part, once per part
+}
+
+impl Default for MachineSpeeds {
+    fn default() -> Self {
+        MachineSpeeds {
+            approach_mm_s: 200.0,
+            bending_mm_s: 10.0,
+            return_mm_s: 200.0,
+            opening_mm: 50.0,
+            dwell_s: 0.3,
+            backgauge_mm_s: 500.0,
+            backgauge_settle_s: 0.3,
+            tool_change_s: 30.0,
+            flip_s: 6.0,
+            rotate_s: 4.0,
+            load_unload_s: 12.0,
+        }
+    }
+}
+
+/// Time for one job step, summed over its machine hits.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StepTime {
+    pub sequence_order: usize,
+    pub hits: usize,
+    pub backgauge_s: f64,
+    pub handling_s: f64,   // Flips and rotations before the hits
+    pub tool_change_s: f64,
+    pub ram_s: f64,        // Approach, bending, dwell and return
+}
+
+impl StepTime {
+    pub fn total_s(&self) -> f64 {
+        self.backgauge_s + self.handling_s + self.tool_change_s + self.ram_s
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CycleTime {
+    pub steps: Vec<StepTime>, // In sequence order
+    pub load_unload_s: f64,
+    pub flips: usize,
+    pub rotations: usize,
+    pub tool_changes: usize,
+}
+
+impl CycleTime {
+    /// Time per part, from taking the blank to stacking the bent part.
+    pub fn total_s(&self) -> f64 {
+        self.load_unload_s + self.steps.iter().map(StepTime::total_s).sum::<f64>()
+    }
+}
+
+/// Ram travel from touching the sheet to the bottom of the hit: the punch sinks
+/// into the V until the flanges reach the target angle.
+pub fn bending_stroke_mm(hit: &BendStep, v_opening_mm: f64, thickness_mm: f64) -> f64 {
+    if hit.method == BendMethod::Hemming {
+        return v_opening_mm / 2.0 + thickness_mm; // Flattening closes a pre-bend of about V/2 high
+    }
+    let half_deflection = ((180.0 - hit.target_angle_deg) / 2.0).clamp(0.0, MAX_HALF_DEFLECTION_DEG);
+    v_opening_mm / 2.0 * half_deflection.to_radians().tan() + thickness_mm
+}
+
+/// The backgauge stops the end of the shorter flange: (gauge distance, gauging the far end).
+pub fn backgauge_setting(hit: &BendStep, length_mm: f64) -> (f64, bool) {
+    let far = length_mm - hit.position_mm;
+    if far < hit.position_mm { (far, true) } else { (hit.position_mm, false) }
+}
+
+/// Estimates the cycle time of one part. The backgauge and the part start where
+/// the previous part of the batch ended, so repeat parts don't pay for a home move.
+pub fn estimate(setup: &MachineSetup, job: &Job, speeds: &MachineSpeeds) -> CycleTime {
+    let hits = geometry::expand_machine_steps(job);
+    let length = job.sheet.original_length_mm;
+    let thickness = job.sheet.thickness_mm;
+    let mut cycle = CycleTime { load_unload_s: speeds.load_unload_s, ..Default::default() };
+
+    let (Some(first), Some(last)) = (hits.first(), hits.last()) else { return cycle };
+    let (mut gauge_x, _) = backgauge_setting(last, length);
+    let (_, mut gauge_far_end) = backgauge_setting(first, length); // A new blank is loaded the right way round
+    let mut direction = first.direction.clone();
+    let mut flattening = last.method == BendMethod::Hemming; // Hemming hits need the flattening tools
+
+    for hit in &hits {
+        if cycle.steps.last().is_none_or(|s| s.sequence_order != hit.sequence_order) {
+            cycle.steps.push(StepTime { sequence_order: hit.sequence_order, ..Default::default() });
+        }
+        let step = cycle.steps.last_mut().expect("pushed above");
+        step.hits += 1;
+
+        let (x, far_end) = backgauge_setting(hit, length);
+        let travel = (x - gauge_x).abs();
+        if travel > 1e-6 {
+            step.backgauge_s += travel / speeds.backgauge_mm_s + speeds.backgauge_settle_s;
+        }
+        if hit.direction != direction {
+            step.handling_s += speeds.flip_s;
+            cycle.flips += 1;
+        }
+        if far_end != gauge_far_end {
+            step.handling_s += speeds.rotate_s;
+            cycle.rotations += 1;
+        }
+        let hemming = hit.method == BendMethod::Hemming;
+        if hemming != flattening {
+            step.tool_change_s += speeds.tool_change_s;
+            cycle.tool_changes += 1;
+        }
+
+        let stroke = bending_stroke_mm(hit, setup.die.v_opening_mm, thickness);
+        step.ram_s += speeds.opening_mm / speeds.approach_mm_s
+            + stroke / speeds.bending_mm_s
+            + speeds.dwell_s
+            + (speeds.opening_mm + stroke) / speeds.return_mm_s;
+
+        (gauge_x, gauge_far_end, direction, flattening) = (x, far_end, hit.direction.clone(), hemming);
+    }
+    cycle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{self, BendDirection, BendFeature, Die, HemStyle, MaterialName};
+
+    fn hit(sequence_order: usize, position_mm: f64, direction: BendDirection, feature: BendFeature) -> BendStep {
+        BendStep {
+            sequence_order,
+            position_mm,
+            target_angle_deg: 90.0,
+            radius_mm: 2.0,
+            direction,
+            method: BendMethod::AirBending,
+            feature,
+        }
+    }
+
+    fn die() -> Die {
+        Die { name: "V16".to_string(), v_opening_mm: 16.0, angle_deg: 88.0, shoulder_radius_mm: 2.0 }
+    }
+
+    #[test]
+    fn stroke_follows_the_deflection() {
+        let mut step = hit(1, 100.0, BendDirection::Up, BendFeature::Single);
+        // 45° half deflection: the punch sinks V/2 into the die, plus the sheet
+        assert!((bending_stroke_mm(&step, 16.0, 2.0) - 10.0).abs() < 1e-9);
+        step.target_angle_deg = 180.0;
+        assert!((bending_stroke_mm(&step, 16.0, 2.0) - 2.0).abs() < 1e-9);
+        step.target_angle_deg = 0.0;
+        let capped = 8.0 * MAX_HALF_DEFLECTION_DEG.to_radians().tan() + 2.0;
+        assert!((bending_stroke_mm(&step, 16.0, 2.0) - capped).abs() < 1e-9);
+        step.method = BendMethod::Hemming;
+        assert!((bending_stroke_mm(&step, 16.0, 2.0) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn backgauge_stops_the_shorter_flange() {
+        let at = |position_mm| hit(1, position_mm, BendDirection::Up, BendFeature::Single);
+        assert_eq!(backgauge_setting(&at(100.0), 300.0), (100.0, false));
+        assert_eq!(backgauge_setting(&at(250.0), 300.0), (50.0, true));
+        assert_eq!(backgauge_setting(&at(150.0), 300.0), (150.0, false));
+    }
+
+    #[test]
+    fn counts_flips_rotations_and_tool_changes() {
+        let materials = model::default_materials();
+        let punches = model::default_punches();
+        let die = die();
+        let job = Job {
+            steps: vec![
+                hit(1, 50.0, BendDirection::Up, BendFeature::Single),
+                hit(2, 250.0, BendDirection::Up, BendFeature::Single),  // Far end: rotate
+                hit(3, 100.0, BendDirection::Down, BendFeature::Single), // Near end again, and flip
+                hit(4, 30.0, BendDirection::Up, BendFeature::Hem(HemStyle::Closed)), // Flip back, then the flattening tools
+            ],
+            ..Job::default()
+        };
+        let setup = MachineSetup { sheet: &job.sheet, material: &materials[&MaterialName::Steel], punch: &punches[0], die: &die };
+        let speeds = MachineSpeeds::default();
+        let cycle = estimate(&setup, &job, &speeds);
+
+        assert_eq!((cycle.flips, cycle.rotations), (2, 2));
+        // The batch ends on the flattening tools, so every part changes to and from them
+        assert_eq!(cycle.tool_changes, 2);
+        assert_eq!(cycle.steps.iter().map(|s| s.hits).collect::<Vec<_>>(), vec![1, 1, 1, 2]);
+        // The backgauge starts where the last hit of the previous part left it (30 mm)
+        assert!((cycle.steps[0].backgauge_s - (20.0 / speeds.backgauge_mm_s + speeds.backgauge_settle_s)).abs() < 1e-9);
+        assert_eq!(cycle.steps[0].handling_s, 0.0);
+        assert_eq!(cycle.steps[0].tool_change_s, speeds.tool_change_s);
+        let steps_s: f64 = cycle.steps.iter().map(StepTime::total_s).sum();
+        assert!((cycle.total_s() - steps_s - speeds.load_unload_s).abs() < 1e-9);
+    }
+
+    #[test]
+    fn job_without_steps_only_loads_and_unloads() {
+        let materials = model::default_materials();
+        let punches = model::default_punches();
+        let die = die();
+        let job = Job { steps: Vec::new(), ..Job::default() };
+        let setup = MachineSetup { sheet: &job.sheet, material: &materials[&MaterialName::Steel], punch: &punches[0], die: &die };
+        let cycle = estimate(&setup, &job, &MachineSpeeds::default());
+        assert!(cycle.steps.is_empty());
+        assert_eq!(cycle.total_s(), MachineSpeeds::default().load_unload_s);
+    }
+}
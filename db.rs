@@ -1,5 +1,13 @@
-// src/db.rs (Conceptual - can be expanded for file I/O)
-use crate::state::Job; // Assuming Job definition is in state or models
+// src/db.rs
+use crate::model::Job;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+/// Version written to new job files. Bump it together with a new entry in `MIGRATIONS`
+/// whenever a change to `Job` would stop older files from deserializing.
+pub const JOB_FORMAT_VERSION: u32 = 3;
+const FORMAT_VERSION_KEY: &str = "format_version";
 
 #[derive(Debug, thiserror::Error)]
 pub enum JobStorageError {
@@ -11,40 +19,184 @@ pub enum JobStorageError {
     DeserializationError(String),
     #[error("Job not found: {0}")]
     NotFound(String),
+    #[error("Job file has format version {found}, this version supports up to {supported}. Update the simulator to open it.")]
+    NewerVersion { found: u32, supported: u32 },
+    #[error("Not a job file: {0}")]
+    UnknownFormat(String),
+}
+
+#[derive(Serialize)]
+struct JobFile<'a> {
+    format_version: u32,
+    #[serde(flatten)]
+    job: &'a Job,
+}
+
+// --- Migrations ---
+
+/// `MIGRATIONS[n]` upgrades a version `n + 1` file to version `n + 2`.
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+];
+const _: () = assert!(MIGRATIONS.len() == JOB_FORMAT_VERSION as usize - 1);
+
+/// Version 1 (no `format_version` key) predates bending methods and compound features.
+fn migrate_v1_to_v2(job: &mut Map<String, Value>) {
+    if let Some(Value::Array(steps)) = job.get_mut("steps") {
+        for step in steps.iter_mut().filter_map(Value::as_object_mut) {
+            step.entry("method").or_insert_with(|| Value::from("AirBending"));
+            step.entry("feature").or_insert_with(|| Value::from("Single"));
+        }
+    }
+}
+
+/// Version 2 sheets have no rolling direction.
+fn migrate_v2_to_v3(job: &mut Map<String, Value>) {
+    if let Some(Value::Object(sheet)) = job.get_mut("sheet") {
+        sheet.entry("rolling_direction").or_insert_with(|| Value::from("Unknown"));
+    }
 }
 
-// Simulate saving a job
-pub fn save_job_to_file(job: &Job, file_path: &str) -> Result<(), JobStorageError> {
-    println!("Simulating: Saving job '{}' to file '{}'", job.name, file_path);
-    // In a real app:
-    // let json_data = serde_json::to_string_pretty(job).map_err(|e| JobStorageError::SerializationError(e.to_string()))?;
-    // std::fs::write(file_path, json_data)?;
-    if job.name.contains("fail_save") { // Test error
-        return Err(JobStorageError::IoError(std::io::Error::new(std::io::ErrorKind::Other, "Simulated save failure")));
+fn format_version(job: &Map<String, Value>) -> Result<u32, JobStorageError> {
+    match job.get(FORMAT_VERSION_KEY) {
+        None if ["name", "sheet", "steps"].iter().all(|key| job.contains_key(*key)) => Ok(1),
+        None => Err(JobStorageError::UnknownFormat("missing format_version and job fields".to_string())),
+        Some(value) => value.as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v >= 1)
+            .ok_or_else(|| JobStorageError::UnknownFormat(format!("invalid format_version {}", value))),
+    }
+}
+
+/// Brings a parsed job file up to `JOB_FORMAT_VERSION` and deserializes it.
+pub fn job_from_json(json_data: &str) -> Result<Job, JobStorageError> {
+    let value: Value = serde_json::from_str(json_data).map_err(|e| JobStorageError::DeserializationError(e.to_string()))?;
+    let Value::Object(mut job) = value else {
+        return Err(JobStorageError::UnknownFormat("expected a JSON object".to_string()));
+    };
+    let found = format_version(&job)?;
+    if found > JOB_FORMAT_VERSION {
+        return Err(JobStorageError::NewerVersion { found, supported: JOB_FORMAT_VERSION });
+    }
+    for migrate in &MIGRATIONS[found as usize - 1..] {
+        migrate(&mut job);
     }
+    job.remove(FORMAT_VERSION_KEY);
+    serde_json::from_value(Value::Object(job)).map_err(|e| JobStorageError::DeserializationError(e.to_string()))
+}
+
+pub fn job_to_json(job: &Job) -> Result<String, JobStorageError> {
+    serde_json::to_string_pretty(&JobFile { format_version: JOB_FORMAT_VERSION, job })
+        .map_err(|e| JobStorageError::SerializationError(e.to_string()))
+}
+
+// --- Files ---
+
+pub fn save_job_to_file(job: &Job, file_path: impl AsRef<Path>) -> Result<(), JobStorageError> {
+    std::fs::write(file_path, job_to_json(job)?)?;
     Ok(())
 }
 
-// Simulate loading a job
-pub fn load_job_from_file(file_path: &str) -> Result<Job, JobStorageError> {
-    println!("Simulating: Loading job from file '{}'", file_path);
-    // In a real app:
-    // let json_data = std::fs::read_to_string(file_path)?;
-    // let job: Job = serde_json::from_str(&json_data).map_err(|e| JobStorageError::DeserializationError(e.to_string()))?;
-    // Ok(job)
-
-    if file_path.contains("nonexistent") {
-        return Err(JobStorageError::NotFound(file_path.to_string()));
-    }
-    // Return a default job for simulation purposes
-    let mut default_job = Job::default();
-    default_job.name = format!("LoadedJob_{}", file_path.split('/').last().unwrap_or("unknown"));
-    default_job.steps.push(crate::state::BendStep {
-        sequence_order: 1,
-        position_mm: 50.0,
-        target_angle_deg: 90.0,
-        radius_mm: 2.0,
-        direction: crate::state::BendDirection::Up,
-    });
-    Ok(default_job)
+pub fn load_job_from_file(file_path: impl AsRef<Path>) -> Result<Job, JobStorageError> {
+    let file_path = file_path.as_ref();
+    let json_data = std::fs::read_to_string(file_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => JobStorageError::NotFound(file_path.display().to_string()),
+        _ => JobStorageError::IoError(e),
+    })?;
+    job_from_json(&json_data)
+}
+
+/// JSON files directly inside `dir`, sorted by name.
+pub fn list_job_files(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, JobStorageError> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BendDirection, BendFeature, BendMethod, BendStep, RollingDirection};
+
+    const V1_JOB: &str = r#"{
+        "name": "Old",
+        "sheet": { "id": "S1", "original_length_mm": 200.0, "thickness_mm": 1.5, "width_mm": 50.0, "material_name": "Steel" },
+        "steps": [ { "sequence_order": 1, "position_mm": 50.0, "target_angle_deg": 90.0, "radius_mm": 1.5, "direction": "Up" } ]
+    }"#;
+
+    #[test]
+    fn v1_file_gets_method_and_feature() {
+        let job = job_from_json(V1_JOB).unwrap();
+        assert_eq!(job.name, "Old");
+        assert_eq!(job.steps.len(), 1);
+        assert_eq!(job.steps[0].method, BendMethod::AirBending);
+        assert_eq!(job.steps[0].feature, BendFeature::Single);
+    }
+
+    #[test]
+    fn newer_version_is_refused() {
+        let json = V1_JOB.replacen('{', r#"{ "format_version": 99,"#, 1);
+        match job_from_json(&json) {
+            Err(JobStorageError::NewerVersion { found, supported }) => {
+                assert_eq!(found, 99);
+                assert_eq!(supported, JOB_FORMAT_VERSION);
+            }
+            other => panic!("expected NewerVersion, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn non_job_json_is_unknown_format() {
+        assert!(matches!(job_from_json("{}"), Err(JobStorageError::UnknownFormat(_))));
+        let json = V1_JOB.replacen('{', r#"{ "format_version": 0,"#, 1);
+        assert!(matches!(job_from_json(&json), Err(JobStorageError::UnknownFormat(_))));
+        assert!(matches!(job_from_json("[1, 2]"), Err(JobStorageError::UnknownFormat(_))));
+    }
+
+    #[test]
+    fn corrupt_json_is_a_deserialization_error() {
+        let truncated = &V1_JOB[..V1_JOB.len() / 2];
+        assert!(matches!(job_from_json(truncated), Err(JobStorageError::DeserializationError(_))));
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let mut job = Job { name: "Round trip".to_string(), ..Job::default() };
+        job.steps.push(BendStep {
+            sequence_order: 1,
+            position_mm: 100.0,
+            target_angle_deg: 135.0,
+            radius_mm: 2.0,
+            direction: BendDirection::Down,
+            method: BendMethod::Bottoming,
+            feature: BendFeature::Joggle { offset_mm: 4.0 },
+        });
+        let path = std::env::temp_dir().join(format!("vouwbank-db-test-{}.json", std::process::id()));
+        save_job_to_file(&job, &path).unwrap();
+        let loaded = load_job_from_file(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(loaded.unwrap(), job);
+        assert!(job_to_json(&job).unwrap().contains(&format!("\"format_version\": {}", JOB_FORMAT_VERSION)));
+    }
+
+    #[test]
+    fn v2_sheet_gets_unknown_rolling_direction() {
+        let json = V1_JOB.replacen('{', r#"{ "format_version": 2,"#, 1).replace("\"direction\": \"Up\"", "\"direction\": \"Up\", \"method\": \"Bottoming\", \"feature\": \"Single\"");
+        let job = job_from_json(&json).unwrap();
+        assert_eq!(job.sheet.rolling_direction, RollingDirection::Unknown);
+        assert_eq!(job.steps[0].method, BendMethod::Bottoming); // Version 2 fields are kept
+    }
+
+    #[test]
+    fn missing_file_is_not_found() {
+        let path = std::env::temp_dir().join("vouwbank-db-test-does-not-exist.json");
+        assert!(matches!(load_job_from_file(&path), Err(JobStorageError::NotFound(_))));
+    }
 }
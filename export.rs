@@ -0,0 +1,386 @@
+// src/export.rs
+use crate::cycle_time::{self, MachineSpeeds};
+use crate::geometry;
+use crate::handling;
+use crate::model::{BendDirection, BendMethod, Job, MachineSetup};
+use crate::nesting::Nest;
+use crate::physics;
+use crate::simulation::SimulationResult;
+use crate::units::LengthUnit;
+use image::{Rgba, RgbaImage};
+use std::fmt::Write;
+
+const PROFILE_MARGIN_MM: f64 = 10.0;
+
+// --- Text Report ---
+
+/// Human readable summary of a simulated job: setup, hits, warnings and the cycle
+/// time with `speeds`. Lengths are written in `unit`; tool names (V opening,
+/// radius) keep their metric designation.
+pub fn simulation_report(setup: &MachineSetup, job: &Job, result: &SimulationResult, speeds: &MachineSpeeds, unit: LengthUnit) -> String {
+    let MachineSetup { sheet, material, punch, die } = *setup;
+    let mut report = String::new();
+    let _ = writeln!(report, "Job: {}", job.name);
+    let _ = writeln!(
+        report,
+        "Sheet: {} - {} {}×{}, t={}",
+        sheet.id, material.name, unit.format(sheet.original_length_mm, 1), unit.format(sheet.width_mm, 1), unit.format(sheet.thickness_mm, 2)
+    );
+    let _ = writeln!(report, "Tooling: punch {} ({}°, R{}), die {} (V{}, {}°)", punch.name, punch.angle_deg, punch.radius_mm, die.name, die.v_opening_mm, die.angle_deg);
+    let _ = writeln!(report, "Bend steps: {}, machine hits: {}", job.steps.len(), result.hits.len());
+    let _ = writeln!(report);
+
+    let pos_header = format!("Pos ({})", unit.symbol());
+    let radius_header = format!("R ({})", unit.symbol());
+    let _ = writeln!(report, "{:>4} {:>9} {:>8} {:>8} {:>5} {:>12} {:>9}", "Step", pos_header, "Angle", radius_header, "Dir", "Method", "Force");
+    for hit in &result.hits {
+        let force_kn = physics::bend_force_kn(sheet, material, die, hit.method);
+        let _ = writeln!(
+            report,
+            "{:>4} {:>9} {:>7.1}° {:>8} {:>5} {:>12} {:>6.0}kN",
+            hit.sequence_order, unit.format_value(hit.position_mm, 2), hit.target_angle_deg, unit.format_value(hit.radius_mm, 2),
+            hit.direction.to_string(), hit.method.to_string(), force_kn
+        );
+    }
+
+    let _ = writeln!(report);
+    if let Some([min_x, min_y, max_x, max_y]) = geometry::profile_bounds(&result.profile) {
+        let _ = writeln!(report, "Profile extents: {} × {}", unit.format(max_x - min_x, 1), unit.format(max_y - min_y, 1));
+    }
+
+    let cycle = cycle_time::estimate(setup, job, speeds);
+    let _ = writeln!(
+        report,
+        "Estimated cycle time: {:.1} s per part ({} flips, {} rotations, {} tool changes)",
+        cycle.total_s(), cycle.flips, cycle.rotations, cycle.tool_changes
+    );
+    let part = handling::analyse(setup, job);
+    let _ = writeln!(
+        report,
+        "Part weight: {:.2} kg, centre of gravity at ({}, {})",
+        part.mass_kg, unit.format(part.cog_mm[0], 1), unit.format(part.cog_mm[1], 1)
+    );
+
+    let _ = writeln!(report);
+    if result.warnings.is_empty() {
+        let _ = writeln!(report, "No warnings.");
+    } else {
+        let _ = writeln!(report, "Warnings:");
+        for warning in &result.warnings {
+            let _ = writeln!(report, "  - {}", warning);
+        }
+    }
+    report
+}
+
+// --- Machine Program ---
+
+fn method_code(method: BendMethod) -> &'static str {
+    match method {
+        BendMethod::AirBending => "AIR",
+        BendMethod::Bottoming => "BOTTOM",
+        BendMethod::Coining => "COIN",
+        BendMethod::Hemming => "HEM",
+    }
+}
+
+fn unit_code(unit: LengthUnit) -> &'static str {
+    match unit {
+        LengthUnit::Millimeter => "MM",
+        LengthUnit::Inch => "INCH",
+    }
+}
+
+/// Block-numbered program for the press brake controller: one `BEND` block per
+/// machine hit with the backgauge distance to the bend line (X), the sheet end
+/// against the backgauge (`GAUGE=START|END`), included angle (A), inner radius (R)
+/// and the estimated tonnage (F). The `UNITS` block tells the controller how to
+/// read the lengths.
+pub fn machine_program(setup: &MachineSetup, job: &Job, result: &SimulationResult, unit: LengthUnit) -> String {
+    let MachineSetup { sheet, material, punch, die } = *setup;
+    let mut program = String::new();
+    let _ = writeln!(program, "; JOB \"{}\"", job.name);
+    let _ = writeln!(
+        program,
+        "; MATERIAL {} T={} L={} W={}",
+        material.name, unit.format_value(sheet.thickness_mm, 2), unit.format_value(sheet.original_length_mm, 2), unit.format_value(sheet.width_mm, 2)
+    );
+    let mut block = 10;
+    let _ = writeln!(program, "N{} UNITS {}", block, unit_code(unit));
+    block += 10;
+    let _ = writeln!(program, "N{} TOOL PUNCH=\"{}\" DIE=\"{}\"", block, punch.name, die.name);
+    for hit in &result.hits {
+        block += 10;
+        let direction = match hit.direction {
+            BendDirection::Up => "UP",
+            BendDirection::Down => "DOWN",
+        };
+        let (gauge_mm, gauge_far_end) = cycle_time::backgauge_setting(hit, sheet.original_length_mm);
+        let gauged_end = if gauge_far_end { "END" } else { "START" };
+        let force_kn = physics::bend_force_kn(sheet, material, die, hit.method);
+        let _ = writeln!(
+            program,
+            "N{} BEND SEQ={} X={} GAUGE={} A={:.2} R={} DIR={} MODE={} F={:.0}",
+            block, hit.sequence_order, unit.format_value(gauge_mm, 2), gauged_end, hit.target_angle_deg, unit.format_value(hit.radius_mm, 2),
+            direction, method_code(hit.method), force_kn
+        );
+    }
+    let _ = writeln!(program, "N{} END", block + 10);
+    program
+}
+
+// --- Profile Drawings ---
+
+/// Profile as an SVG drawing in mm, y pointing up like in `geometry::profile_polyline`.
+pub fn profile_svg(points: &[[f64; 2]], stroke_width_mm: f64) -> String {
+    let [min_x, min_y, max_x, max_y] = geometry::profile_bounds(points).unwrap_or([0.0; 4]);
+    let width = max_x - min_x + 2.0 * PROFILE_MARGIN_MM;
+    let height = max_y - min_y + 2.0 * PROFILE_MARGIN_MM;
+    let path: Vec<String> = points.iter()
+        .map(|[x, y]| format!("{:.3},{:.3}", x - min_x + PROFILE_MARGIN_MM, max_y - y + PROFILE_MARGIN_MM))
+        .collect();
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w:.3}mm" height="{h:.3}mm" viewBox="0 0 {w:.3} {h:.3}">"#,
+        w = width, h = height
+    );
+    let _ = writeln!(
+        svg,
+        r#"  <polyline points="{}" fill="none" stroke="black" stroke-width="{:.3}" stroke-linejoin="round"/>"#,
+        path.join(" "), stroke_width_mm
+    );
+    let _ = writeln!(svg, "</svg>");
+    svg
+}
+
+/// Rasterises the profile into a white image of the given size, scaled to fit.
+pub fn profile_image(points: &[[f64; 2]], width_px: u32, height_px: u32) -> RgbaImage {
+    let mut image = RgbaImage::from_pixel(width_px, height_px, Rgba([255, 255, 255, 255]));
+    let Some([min_x, min_y, max_x, max_y]) = geometry::profile_bounds(points) else { return image };
+
+    let span = (max_x - min_x).max(max_y - min_y).max(1e-6);
+    let scale = width_px.min(height_px) as f64 * 0.9 / span;
+    let mid = [(min_x + max_x) / 2.0, (min_y + max_y) / 2.0];
+    let to_px = |[x, y]: [f64; 2]| [
+        width_px as f64 / 2.0 + (x - mid[0]) * scale,
+        height_px as f64 / 2.0 - (y - mid[1]) * scale,
+    ];
+
+    let line_width_px = (width_px.min(height_px) as f64 / 200.0).max(1.0);
+    for segment in points.windows(2) {
+        let [x0, y0] = to_px(segment[0]);
+        let [x1, y1] = to_px(segment[1]);
+        let steps = (x1 - x0).abs().max((y1 - y0).abs()).ceil().max(1.0) as usize;
+        for i in 0..=steps {
+            let t = i as f64 / steps as f64;
+            stamp_dot(&mut image, x0 + (x1 - x0) * t, y0 + (y1 - y0) * t, line_width_px / 2.0);
+        }
+    }
+    image
+}
+
+fn stamp_dot(image: &mut RgbaImage, cx: f64, cy: f64, radius: f64) {
+    let r = radius.ceil() as i64;
+    for dy in -r..=r {
+        for dx in -r..=r {
+            if (dx * dx + dy * dy) as f64 > radius * radius + 0.5 {
+                continue;
+            }
+            let (x, y) = (cx.round() as i64 + dx, cy.round() as i64 + dy);
+            if x >= 0 && y >= 0 && (x as u32) < image.width() && (y as u32) < image.height() {
+                image.put_pixel(x as u32, y as u32, Rgba([0, 0, 0, 255]));
+            }
+        }
+    }
+}
+
+// --- PDF Documents ---
+
+const PDF_PAGE_SIZE_PT: [f64; 2] = [595.0, 842.0]; // A4
+const PDF_MARGIN_PT: f64 = 56.0;
+const PDF_TITLE_SIZE_PT: f64 = 16.0;
+const PDF_TEXT_SIZE_PT: f64 = 11.0;
+const PDF_LINE_SPACING: f64 = 1.4;
+
+/// Text as a PDF string literal in WinAnsi encoding; characters it lacks become '?'.
+fn pdf_text(text: &str) -> Vec<u8> {
+    let mut bytes = vec![b'('];
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => bytes.extend([b'\\', c as u8]),
+            '€' => bytes.push(0x80),
+            ' '..='~' | '\u{a0}'..='\u{ff}' => bytes.push(c as u32 as u8),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes.push(b')');
+    bytes
+}
+
+/// Single A4 page with a title and lines of Helvetica text, written by hand so
+/// quotes need no PDF library. Lines past the bottom of the page are dropped.
+pub fn text_pdf(title: &str, lines: &[String]) -> Vec<u8> {
+    let [page_width, page_height] = PDF_PAGE_SIZE_PT;
+    let leading = PDF_TEXT_SIZE_PT * PDF_LINE_SPACING;
+    let max_lines = ((page_height - 2.0 * PDF_MARGIN_PT - 2.0 * PDF_TITLE_SIZE_PT) / leading) as usize;
+
+    let mut content = format!("BT\n/F1 {} Tf\n{} {} Td\n", PDF_TITLE_SIZE_PT, PDF_MARGIN_PT, page_height - PDF_MARGIN_PT).into_bytes();
+    content.extend(pdf_text(title));
+    content.extend(format!(" Tj\n/F1 {} Tf\n{:.1} TL\n0 {} Td\n", PDF_TEXT_SIZE_PT, leading, -2.0 * PDF_TITLE_SIZE_PT).into_bytes());
+    for line in lines.iter().take(max_lines) {
+        content.extend(pdf_text(line));
+        content.extend(b" Tj T*\n");
+    }
+    content.extend(b"ET\n");
+
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        format!("<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 4 0 R >> >> /Contents 5 0 R >>", page_width, page_height).into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec(),
+    ];
+    let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+    stream.extend(content);
+    stream.extend(b"\nendstream");
+    objects.push(stream);
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", i + 1).into_bytes());
+        pdf.extend(object);
+        pdf.extend(b"\nendobj\n");
+    }
+    let xref_offset = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    pdf.extend(format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref_offset).into_bytes());
+    pdf
+}
+
+// --- Nest Layouts ---
+
+const DXF_MAX_LABEL_HEIGHT_MM: f64 = 20.0;
+
+fn dxf_rectangle(dxf: &mut String, layer: &str, x: f64, y: f64, length: f64, width: f64) {
+    let _ = writeln!(dxf, "0\nPOLYLINE\n8\n{}\n66\n1\n10\n0.0\n20\n0.0\n30\n0.0\n70\n1", layer);
+    for [vx, vy] in [[x, y], [x + length, y], [x + length, y + width], [x, y + width]] {
+        let _ = writeln!(dxf, "0\nVERTEX\n8\n{}\n10\n{:.3}\n20\n{:.3}", layer, vx, vy);
+    }
+    let _ = writeln!(dxf, "0\nSEQEND\n8\n{}", layer);
+}
+
+/// One plate of a nest as an R12 DXF in mm for the laser: the plate outline on
+/// layer PLATE, closed blank outlines on PARTS and job names on LABELS.
+pub fn nest_dxf(nest: &Nest, plate_index: usize) -> String {
+    let mut dxf = String::new();
+    let _ = writeln!(dxf, "0\nSECTION\n2\nHEADER\n9\n$ACADVER\n1\nAC1009\n0\nENDSEC");
+    let _ = writeln!(dxf, "0\nSECTION\n2\nENTITIES");
+    dxf_rectangle(&mut dxf, "PLATE", 0.0, 0.0, nest.plate.length_mm, nest.plate.width_mm);
+    if let Some(plate) = nest.plates.get(plate_index) {
+        for placement in &plate.placements {
+            let blank = &nest.blanks[placement.blank];
+            let (length, width) = placement.size_mm(blank);
+            dxf_rectangle(&mut dxf, "PARTS", placement.x_mm, placement.y_mm, length, width);
+            let height = (width / 4.0).min(DXF_MAX_LABEL_HEIGHT_MM);
+            let _ = writeln!(
+                dxf,
+                "0\nTEXT\n8\nLABELS\n10\n{:.3}\n20\n{:.3}\n40\n{:.3}\n1\n{}",
+                placement.x_mm + height / 2.0, placement.y_mm + height / 2.0, height, blank.job_name.replace(['\n', '\r'], " ")
+            );
+        }
+    }
+    let _ = writeln!(dxf, "0\nENDSEC\n0\nEOF");
+    dxf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{self, BendFeature, BendStep, MaterialName};
+    use crate::simulation;
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).position(|w| w == needle)
+    }
+
+    #[test]
+    fn pdf_cross_reference_points_at_the_objects() {
+        let lines = vec!["Quantity: 10".to_string(), "Unit price: 12.50 €".to_string(), "(draft)".to_string()];
+        let pdf = text_pdf("Quote", &lines);
+        let xref = find(&pdf, b"xref\n").unwrap();
+        let tail = String::from_utf8_lossy(&pdf[xref..]); // The € is not UTF-8, so offsets come from the bytes
+        let startxref: usize = tail.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        assert_eq!(startxref, xref);
+
+        let entries: Vec<usize> = tail.lines()
+            .filter(|line| line.ends_with(" n "))
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        assert_eq!(entries.len(), 5);
+        for (i, offset) in entries.into_iter().enumerate() {
+            let header = format!("{} 0 obj\n", i + 1);
+            assert!(pdf[offset..].starts_with(header.as_bytes()), "object {} not at {}", i + 1, offset);
+        }
+    }
+
+    #[test]
+    fn pdf_stream_length_matches() {
+        let pdf = text_pdf("Quote", &["Bracket".to_string()]);
+        let text = String::from_utf8_lossy(&pdf);
+        let length: usize = text.split("/Length ").nth(1).unwrap().split(' ').next().unwrap().parse().unwrap();
+        let start = find(&pdf, b"stream\n").unwrap() + "stream\n".len();
+        let end = find(&pdf, b"\nendstream").unwrap();
+        assert_eq!(end - start, length);
+    }
+
+    #[test]
+    fn report_columns_are_in_english() {
+        let materials = model::default_materials();
+        let (punches, dies) = (model::default_punches(), model::default_dies());
+        let step = BendStep {
+            sequence_order: 1,
+            position_mm: 150.0,
+            target_angle_deg: 90.0,
+            radius_mm: 2.0,
+            direction: BendDirection::Up,
+            method: BendMethod::AirBending,
+            feature: BendFeature::Single,
+        };
+        let job = Job { steps: vec![step], ..Job::default() };
+        let setup = MachineSetup { sheet: &job.sheet, material: &materials[&MaterialName::Steel], punch: &punches[0], die: &dies[0] };
+        let result = simulation::simulate(&setup, &job).unwrap();
+        let report = simulation_report(&setup, &job, &result, &MachineSpeeds::default(), LengthUnit::Millimeter);
+        let header = report.lines().find(|line| line.trim_start().starts_with("Step")).unwrap();
+        let columns: Vec<&str> = header.split_whitespace().collect();
+        assert_eq!(columns, vec!["Step", "Pos", "(mm)", "Angle", "R", "(mm)", "Dir", "Method", "Force"]);
+    }
+
+    #[test]
+    fn program_gauges_the_shorter_flange() {
+        let materials = model::default_materials();
+        let (punches, dies) = (model::default_punches(), model::default_dies());
+        let bend = |sequence_order: usize, position_mm: f64| BendStep {
+            sequence_order,
+            position_mm,
+            target_angle_deg: 90.0,
+            radius_mm: 2.0,
+            direction: BendDirection::Up,
+            method: BendMethod::AirBending,
+            feature: BendFeature::Single,
+        };
+        // 300mm sheet: the first bend is gauged from the start, the second from the far end
+        let job = Job { steps: vec![bend(1, 50.0), bend(2, 220.0)], ..Job::default() };
+        let setup = MachineSetup { sheet: &job.sheet, material: &materials[&MaterialName::Steel], punch: &punches[0], die: &dies[0] };
+        let result = simulation::simulate(&setup, &job).unwrap();
+        let program = machine_program(&setup, &job, &result, LengthUnit::Millimeter);
+        let bends: Vec<&str> = program.lines().filter(|line| line.contains(" BEND ")).collect();
+        assert!(bends[0].contains("X=50.00 GAUGE=START"), "{}", bends[0]);
+        assert!(bends[1].contains("X=80.00 GAUGE=END"), "{}", bends[1]);
+    }
+}
@@ -0,0 +1,405 @@
+// src/geometry.rs
+use crate::model::{BendDirection, BendFeature, BendMethod, BendStep, Die, HemStyle, Job};
+
+// --- Compound Feature Expansion ---
+pub const HEM_PREBEND_ANGLE_DEG: f64 = 30.0;
+const TEARDROP_CLOSE_ANGLE_DEG: f64 = -15.0; // Flange pinched past parallel back onto the sheet
+const ARC_SEGMENTS_PER_90_DEG: usize = 8;
+const NEUTRAL_AXIS_K_FACTOR: f64 = 0.33;
+const BUMP_MAX_PITCH_V_FACTOR: f64 = 0.5; // Hits further apart than V/2 leave visible flats
+const BUMP_MIN_HITS: usize = 2;
+// Air bending needs the flange to rest on both die shoulders; below ~0.7·V it slips into the V.
+const MIN_FLANGE_V_OPENING_FACTOR: f64 = 0.7;
+
+/// Expands a job step into the machine hits that produce it. Hems become a
+/// pre-bend plus a flattening hit, joggles two opposite bends. All hits keep
+/// the sequence order of the step they came from.
+pub fn expand_feature(step: &BendStep, thickness_mm: f64) -> Vec<BendStep> {
+    let single = |position_mm: f64, target_angle_deg: f64, radius_mm: f64, direction: BendDirection, method: BendMethod| BendStep {
+        sequence_order: step.sequence_order,
+        position_mm,
+        target_angle_deg,
+        radius_mm,
+        direction,
+        method,
+        feature: BendFeature::Single,
+    };
+
+    match &step.feature {
+        BendFeature::Single => vec![step.clone()],
+        BendFeature::Hem(style) => {
+            let (flat_angle, flat_radius) = match style {
+                HemStyle::Open => (0.0, step.radius_mm),
+                HemStyle::Closed => (0.0, 0.0),
+                HemStyle::Teardrop => (TEARDROP_CLOSE_ANGLE_DEG, step.radius_mm.max(thickness_mm)),
+            };
+            vec![
+                single(step.position_mm, HEM_PREBEND_ANGLE_DEG, step.radius_mm, step.direction.clone(), BendMethod::AirBending),
+                single(step.position_mm, flat_angle, flat_radius, step.direction.clone(), BendMethod::Hemming),
+            ]
+        }
+        BendFeature::Joggle { offset_mm } => {
+            let opposite = match step.direction {
+                BendDirection::Up => BendDirection::Down,
+                BendDirection::Down => BendDirection::Up,
+            };
+            vec![
+                single(step.position_mm, step.target_angle_deg, step.radius_mm, step.direction.clone(), step.method),
+                single(joggle_second_position_mm(step, *offset_mm), step.target_angle_deg, step.radius_mm, opposite, step.method),
+            ]
+        }
+        BendFeature::Bump { hits, pitch_mm, hit_radius_mm } => {
+            let hits = (*hits).max(1);
+            let hit_angle = 180.0 - (180.0 - step.target_angle_deg) / hits as f64;
+            let first = step.position_mm - pitch_mm * (hits as f64 - 1.0) / 2.0;
+            (0..hits)
+                .map(|i| single(first + pitch_mm * i as f64, hit_angle, *hit_radius_mm, step.direction.clone(), BendMethod::AirBending))
+                .collect()
+        }
+    }
+}
+
+/// Second bend line of a joggle: far enough along the sheet that the inclined
+/// web rises by the offset height.
+pub fn joggle_second_position_mm(step: &BendStep, offset_mm: f64) -> f64 {
+    let deflection = (180.0 - step.target_angle_deg).to_radians();
+    let sin = deflection.sin();
+    if sin <= 1e-6 {
+        return step.position_mm + offset_mm;
+    }
+    step.position_mm + offset_mm / sin
+}
+
+/// Bump bending plan for a large-radius step: enough hits along the arc that
+/// none is further than V/2 from the next, each bending an equal share.
+pub fn plan_bump_bending(step: &BendStep, thickness_mm: f64, v_opening_mm: f64, hit_radius_mm: f64) -> BendFeature {
+    let deflection = (180.0 - step.target_angle_deg).to_radians();
+    let arc_length = deflection * (step.radius_mm + thickness_mm * NEUTRAL_AXIS_K_FACTOR);
+    let max_pitch = (v_opening_mm * BUMP_MAX_PITCH_V_FACTOR).max(1e-3);
+    let hits = ((arc_length / max_pitch).ceil() as usize).max(BUMP_MIN_HITS);
+    BendFeature::Bump { hits, pitch_mm: arc_length / hits as f64, hit_radius_mm }
+}
+
+/// All machine hits of the job, in execution order.
+pub fn expand_machine_steps(job: &Job) -> Vec<BendStep> {
+    job.steps.iter()
+        .flat_map(|step| expand_feature(step, job.sheet.thickness_mm))
+        .collect()
+}
+
+// --- Bent Profile ---
+
+/// Final state of every bend line after the given hits, sorted by position:
+/// (position, signed deflection in rad, neutral radius).
+fn bend_lines(job: &Job, machine_steps: &[BendStep]) -> Vec<(f64, f64, f64)> {
+    let neutral_offset = job.sheet.thickness_mm * NEUTRAL_AXIS_K_FACTOR;
+    let mut lines: Vec<(f64, f64, f64)> = Vec::new();
+    for step in machine_steps {
+        let sign = match step.direction {
+            BendDirection::Up => 1.0,
+            BendDirection::Down => -1.0,
+        };
+        let bend = (step.position_mm, sign * (180.0 - step.target_angle_deg).to_radians(), step.radius_mm + neutral_offset);
+        match lines.iter_mut().find(|l| (l.0 - step.position_mm).abs() < 1e-6) {
+            Some(line) => *line = bend,
+            None => lines.push(bend),
+        }
+    }
+    lines.sort_by(|a, b| a.0.total_cmp(&b.0));
+    lines
+}
+
+/// Centre line of the sheet cross-section after the given machine hits have
+/// been made, in mm with the sheet start at the origin and y pointing up.
+/// Later hits on the same bend line replace earlier ones (e.g. hem flattening).
+pub fn profile_polyline(job: &Job, machine_steps: &[BendStep]) -> Vec<[f64; 2]> {
+    let length = job.sheet.original_length_mm;
+    let mut points = vec![[0.0, 0.0]];
+    let mut heading: f64 = 0.0;
+    let mut flat_start = 0.0; // Flat position where the current straight section begins
+    for (position, deflection, radius) in bend_lines(job, machine_steps) {
+        let arc_length = deflection.abs() * radius;
+        let straight = (position - arc_length / 2.0 - flat_start).max(0.0);
+        let [x, y] = *points.last().unwrap();
+        points.push([x + straight * heading.cos(), y + straight * heading.sin()]);
+
+        let segments = ((deflection.abs().to_degrees() / 90.0 * ARC_SEGMENTS_PER_90_DEG as f64).ceil() as usize).max(1);
+        for _ in 0..segments {
+            let delta = deflection / segments as f64;
+            let chord = 2.0 * radius * (delta.abs() / 2.0).sin();
+            let [x, y] = *points.last().unwrap();
+            let mid_heading = heading + delta / 2.0;
+            points.push([x + chord * mid_heading.cos(), y + chord * mid_heading.sin()]);
+            heading += delta;
+        }
+        flat_start = position + arc_length / 2.0;
+    }
+    let remaining = (length - flat_start).max(0.0);
+    let [x, y] = *points.last().unwrap();
+    points.push([x + remaining * heading.cos(), y + remaining * heading.sin()]);
+    points
+}
+
+/// Point and heading (rad) of the centre line at `position_mm` along the flat
+/// blank, in the frame of `profile_polyline` for the same hits.
+pub fn profile_point_at(job: &Job, machine_steps: &[BendStep], position_mm: f64) -> ([f64; 2], f64) {
+    let mut point = [0.0, 0.0];
+    let mut heading: f64 = 0.0;
+    let mut flat_start = 0.0;
+    for (position, deflection, radius) in bend_lines(job, machine_steps) {
+        let arc_length = deflection.abs() * radius;
+        let arc_start = (position - arc_length / 2.0).max(flat_start);
+        if position_mm <= arc_start {
+            break;
+        }
+        let straight = arc_start - flat_start;
+        point = [point[0] + straight * heading.cos(), point[1] + straight * heading.sin()];
+        let along_arc = (position_mm - arc_start).min(arc_length);
+        let delta = if arc_length > 0.0 { deflection * along_arc / arc_length } else { deflection };
+        let chord = 2.0 * radius * (delta.abs() / 2.0).sin();
+        point = [point[0] + chord * (heading + delta / 2.0).cos(), point[1] + chord * (heading + delta / 2.0).sin()];
+        heading += delta;
+        flat_start = position + arc_length / 2.0;
+        if position_mm < flat_start {
+            return (point, heading);
+        }
+    }
+    let straight = (position_mm - flat_start).max(0.0);
+    ([point[0] + straight * heading.cos(), point[1] + straight * heading.sin()], heading)
+}
+
+/// Centroid of a profile centre line, each segment weighted by its length.
+/// For a sheet of constant thickness this is the part's centre of gravity.
+pub fn profile_centroid(points: &[[f64; 2]]) -> Option<[f64; 2]> {
+    let (mut total, mut sum_x, mut sum_y) = (0.0, 0.0, 0.0);
+    for pair in points.windows(2) {
+        let [[x0, y0], [x1, y1]] = [pair[0], pair[1]];
+        let length = (x1 - x0).hypot(y1 - y0);
+        total += length;
+        sum_x += length * (x0 + x1) / 2.0;
+        sum_y += length * (y0 + y1) / 2.0;
+    }
+    (total > 1e-9).then(|| [sum_x / total, sum_y / total])
+}
+
+/// Bounding box of a profile as `[min_x, min_y, max_x, max_y]`, `None` when empty.
+pub fn profile_bounds(points: &[[f64; 2]]) -> Option<[f64; 4]> {
+    let first = points.first()?;
+    Some(points.iter().fold([first[0], first[1], first[0], first[1]], |[min_x, min_y, max_x, max_y], [x, y]| {
+        [min_x.min(*x), min_y.min(*y), max_x.max(*x), max_y.max(*y)]
+    }))
+}
+
+// --- Flange Lengths ---
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BendFlanges {
+    pub sequence_order: usize,
+    pub before_mm: f64, // Flat length towards the previous bend line (or the sheet start)
+    pub after_mm: f64,  // Flat length towards the next bend line (or the sheet end)
+}
+
+impl BendFlanges {
+    pub fn shortest_mm(&self) -> f64 {
+        self.before_mm.min(self.after_mm)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlangeWarning {
+    pub sequence_order: usize,
+    pub flange_mm: f64,
+    pub min_flange_mm: f64,
+}
+
+pub fn min_flange_for_die(die: &Die) -> f64 {
+    die.v_opening_mm * MIN_FLANGE_V_OPENING_FACTOR
+}
+
+/// Flat flange lengths on both sides of every machine hit, measured to the
+/// neighbouring bend line or sheet edge. Hits sharing a position count as one line.
+pub fn compute_flange_lengths(job: &Job) -> Vec<BendFlanges> {
+    let length = job.sheet.original_length_mm;
+    let hits = expand_machine_steps(job);
+    let mut flanges = Vec::new();
+    for job_step in &job.steps {
+        // The hits of a bump bend form one arc, not flanges between each other
+        let is_bump = matches!(job_step.feature, BendFeature::Bump { .. });
+        let neighbours: Vec<f64> = hits.iter()
+            .filter(|h| !(is_bump && h.sequence_order == job_step.sequence_order))
+            .map(|h| h.position_mm)
+            .collect();
+        for step in expand_feature(job_step, job.sheet.thickness_mm) {
+            let previous = neighbours.iter().copied()
+                .filter(|p| *p < step.position_mm)
+                .fold(0.0, f64::max);
+            let next = neighbours.iter().copied()
+                .filter(|p| *p > step.position_mm)
+                .fold(length, f64::min);
+            flanges.push(BendFlanges {
+                sequence_order: step.sequence_order,
+                before_mm: step.position_mm - previous,
+                after_mm: next - step.position_mm,
+            });
+        }
+    }
+    flanges
+}
+
+/// Flanges of the job that are too short to bridge the given die,
+/// one warning per job step (its shortest flange).
+pub fn check_min_flange_lengths(job: &Job, die: &Die) -> Vec<FlangeWarning> {
+    let min_flange_mm = min_flange_for_die(die);
+    let mut warnings: Vec<FlangeWarning> = Vec::new();
+    for flanges in compute_flange_lengths(job).into_iter().filter(|f| f.shortest_mm() < min_flange_mm) {
+        match warnings.iter_mut().find(|w| w.sequence_order == flanges.sequence_order) {
+            Some(w) => w.flange_mm = w.flange_mm.min(flanges.shortest_mm()),
+            None => warnings.push(FlangeWarning { sequence_order: flanges.sequence_order, flange_mm: flanges.shortest_mm(), min_flange_mm }),
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(feature: BendFeature) -> BendStep {
+        BendStep {
+            sequence_order: 3,
+            position_mm: 150.0,
+            target_angle_deg: 90.0,
+            radius_mm: 2.0,
+            direction: BendDirection::Up,
+            method: BendMethod::AirBending,
+            feature,
+        }
+    }
+
+    #[test]
+    fn bump_plan_spreads_the_angle_over_short_pitches() {
+        for (radius, v_opening) in [(50.0, 16.0), (200.0, 24.0), (10.0, 50.0)] {
+            let large = BendStep { radius_mm: radius, ..step(BendFeature::Single) };
+            let bump = plan_bump_bending(&large, 2.0, v_opening, 3.0);
+            let BendFeature::Bump { hits, pitch_mm, hit_radius_mm } = bump else { panic!("not a bump: {:?}", bump) };
+            assert!(hits >= BUMP_MIN_HITS);
+            assert!(pitch_mm <= v_opening / 2.0 + 1e-9, "pitch {} on V{}", pitch_mm, v_opening);
+            assert_eq!(hit_radius_mm, 3.0);
+
+            let expanded = expand_feature(&BendStep { feature: bump, ..large.clone() }, 2.0);
+            assert_eq!(expanded.len(), hits);
+            let deflection: f64 = expanded.iter().map(|hit| 180.0 - hit.target_angle_deg).sum();
+            assert!((deflection - (180.0 - large.target_angle_deg)).abs() < 1e-9);
+            // Centred on the step's bend line
+            let mean = expanded.iter().map(|hit| hit.position_mm).sum::<f64>() / hits as f64;
+            assert!((mean - large.position_mm).abs() < 1e-9);
+            assert!(expanded.windows(2).all(|w| (w[1].position_mm - w[0].position_mm - pitch_mm).abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn hem_is_a_pre_bend_and_a_flattening_hit() {
+        let hits = expand_feature(&step(BendFeature::Hem(HemStyle::Closed)), 2.0);
+        assert_eq!(hits.len(), 2);
+        assert_eq!((hits[0].target_angle_deg, hits[0].method), (HEM_PREBEND_ANGLE_DEG, BendMethod::AirBending));
+        assert_eq!((hits[1].target_angle_deg, hits[1].radius_mm, hits[1].method), (0.0, 0.0, BendMethod::Hemming));
+        assert!(hits.iter().all(|hit| hit.position_mm == 150.0 && hit.sequence_order == 3 && hit.feature == BendFeature::Single));
+
+        let teardrop = expand_feature(&step(BendFeature::Hem(HemStyle::Teardrop)), 3.0);
+        assert_eq!((teardrop[1].target_angle_deg, teardrop[1].radius_mm), (TEARDROP_CLOSE_ANGLE_DEG, 3.0));
+    }
+
+    #[test]
+    fn joggle_is_two_opposite_bends() {
+        let joggle = BendStep { target_angle_deg: 135.0, ..step(BendFeature::Joggle { offset_mm: 10.0 }) };
+        let hits = expand_feature(&joggle, 2.0);
+        assert_eq!(hits.len(), 2);
+        assert_eq!((hits[0].position_mm, &hits[0].direction), (150.0, &BendDirection::Up));
+        assert_eq!(hits[1].direction, BendDirection::Down);
+        // The 45° web rises by the offset
+        let web = hits[1].position_mm - hits[0].position_mm;
+        assert!((web * 45f64.to_radians().sin() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn single_expands_to_itself() {
+        let single = step(BendFeature::Single);
+        assert_eq!(expand_feature(&single, 2.0), vec![single]);
+    }
+
+    #[test]
+    fn point_at_follows_the_polyline_vertices() {
+        let job = Job {
+            steps: vec![
+                BendStep { sequence_order: 1, position_mm: 80.0, ..step(BendFeature::Single) },
+                BendStep { sequence_order: 2, position_mm: 200.0, target_angle_deg: 135.0, direction: BendDirection::Down, ..step(BendFeature::Single) },
+            ],
+            ..Job::default()
+        };
+        let hits = expand_machine_steps(&job);
+        let polyline = profile_polyline(&job, &hits);
+
+        // Flat positions of the polyline vertices: sheet start, arc starts and ends, arc segments, sheet end
+        let mut positions = vec![0.0];
+        for (position, deflection, radius) in bend_lines(&job, &hits) {
+            let arc_length = deflection.abs() * radius;
+            let segments = (deflection.abs().to_degrees() / 90.0 * ARC_SEGMENTS_PER_90_DEG as f64).ceil() as usize;
+            positions.extend((0..=segments).map(|i| position - arc_length / 2.0 + arc_length * i as f64 / segments as f64));
+        }
+        positions.push(job.sheet.original_length_mm);
+        assert_eq!(positions.len(), polyline.len());
+
+        for (position, vertex) in positions.into_iter().zip(&polyline) {
+            let (point, _) = profile_point_at(&job, &hits, position);
+            assert!((point[0] - vertex[0]).hypot(point[1] - vertex[1]) < 1e-6, "{}: {:?} != {:?}", position, point, vertex);
+        }
+        // After the 90° up and 45° down bends the last flange rises at 45°
+        let (_, heading) = profile_point_at(&job, &hits, 250.0);
+        assert!((heading - 45f64.to_radians()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn centroid_of_a_straight_line_is_its_middle() {
+        assert_eq!(profile_centroid(&[[0.0, 0.0], [100.0, 0.0], [300.0, 0.0]]), Some([150.0, 0.0]));
+        assert_eq!(profile_centroid(&[[5.0, 5.0]]), None);
+    }
+
+    fn two_bend_job() -> Job {
+        Job {
+            steps: vec![
+                BendStep { sequence_order: 1, position_mm: 10.0, ..step(BendFeature::Single) },
+                BendStep { sequence_order: 2, position_mm: 200.0, ..step(BendFeature::Single) },
+            ],
+            ..Job::default()
+        }
+    }
+
+    #[test]
+    fn flanges_run_to_the_next_bend_line_or_edge() {
+        let flanges = compute_flange_lengths(&two_bend_job());
+        assert_eq!(flanges, vec![
+            BendFlanges { sequence_order: 1, before_mm: 10.0, after_mm: 190.0 },
+            BendFlanges { sequence_order: 2, before_mm: 190.0, after_mm: 100.0 },
+        ]);
+        assert_eq!(flanges[0].shortest_mm(), 10.0);
+    }
+
+    #[test]
+    fn short_flange_needs_seven_tenths_of_the_v_opening() {
+        let die = |v_opening_mm| Die { name: String::new(), v_opening_mm, angle_deg: 88.0, shoulder_radius_mm: 2.0 };
+        assert!((min_flange_for_die(&die(16.0)) - 11.2).abs() < 1e-9);
+        assert_eq!(check_min_flange_lengths(&two_bend_job(), &die(16.0)), vec![
+            FlangeWarning { sequence_order: 1, flange_mm: 10.0, min_flange_mm: min_flange_for_die(&die(16.0)) },
+        ]);
+        assert!(check_min_flange_lengths(&two_bend_job(), &die(12.0)).is_empty());
+    }
+
+    #[test]
+    fn bump_hits_are_one_bend_line_for_flanges() {
+        let bump = BendStep { sequence_order: 1, ..step(BendFeature::Bump { hits: 5, pitch_mm: 4.0, hit_radius_mm: 3.0 }) };
+        let job = Job { steps: vec![bump], ..Job::default() };
+        let flanges = compute_flange_lengths(&job);
+        assert_eq!(flanges.len(), 5);
+        assert!(flanges.iter().all(|f| f.shortest_mm() > 100.0));
+    }
+}
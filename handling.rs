@@ -0,0 +1,264 @@
+// src/handling.rs
+// Part mass and centre of gravity while it is being bent: whether one operator
+// can lift it, whether it stays against the backgauge on its own and what it
+// takes to support the front flange as it lifts.
+use crate::cycle_time::{self, MachineSpeeds};
+use crate::geometry;
+use crate::model::{BendStep, Job, MachineSetup};
+use crate::physics;
+use std::fmt;
+
+/// Recommended maximum for one person lifting close to the body (EN 1005-2).
+pub const SINGLE_OPERATOR_LIMIT_KG: f64 = 25.0;
+
+const GRAVITY_M_S2: f64 = 9.81;
+const FLANGE_SAMPLES: usize = 64;
+// Flange lift an operator can follow by hand, and what takes a second one
+const SECOND_OPERATOR_TIP_SPEED_M_S: f64 = 0.5;
+const SECOND_OPERATOR_MOMENT_NM: f64 = 50.0;
+const SHEET_FOLLOWER_TIP_SPEED_M_S: f64 = 1.0;
+const SHEET_FOLLOWER_MOMENT_NM: f64 = 150.0;
+
+/// Balance of the part on the die at the start of one job step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepBalance {
+    pub sequence_order: usize,
+    pub cog_mm: [f64; 2],  // In the frame of geometry::profile_polyline, before the step's hits
+    pub overhang_mm: f64,  // CoG past the bend line towards the operator; negative towards the backgauge
+    pub tips_away: bool,   // Unsupported CoG beyond the front die shoulder: the gauged end lifts off the backgauge
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartHandling {
+    pub mass_kg: f64,
+    pub cog_mm: [f64; 2], // Of the finished part
+    pub steps: Vec<StepBalance>, // In sequence order
+}
+
+impl PartHandling {
+    pub fn over_handling_limit(&self) -> bool {
+        self.mass_kg > SINGLE_OPERATOR_LIMIT_KG
+    }
+}
+
+/// Mass of the part and its balance at every step. Bending keeps the volume, so
+/// the mass is that of the blank; the centre of gravity follows the bent profile.
+/// The part lies on the die with the bend line on the punch tip and the flange
+/// tangents level, gauged on its shorter end as in `cycle_time::estimate`. A flat
+/// overhang rests on the front support arms; once it carries bent flanges it
+/// doesn't, and a CoG beyond the front die shoulder tips the part towards the operator.
+pub fn analyse(setup: &MachineSetup, job: &Job) -> PartHandling {
+    let hits = geometry::expand_machine_steps(job);
+    let length = job.sheet.original_length_mm;
+    let shoulder = setup.die.v_opening_mm / 2.0;
+    let mut steps: Vec<StepBalance> = Vec::new();
+
+    for (i, hit) in hits.iter().enumerate() {
+        if steps.last().is_some_and(|s| s.sequence_order == hit.sequence_order) {
+            continue; // The part is balanced once per step, before its first hit
+        }
+        let before = &hits[..i];
+        let Some(cog) = geometry::profile_centroid(&geometry::profile_polyline(job, before)) else { continue };
+        let (point, heading) = geometry::profile_point_at(job, before, hit.position_mm);
+        // Along the profile towards the sheet end is away from the operator when that end is gauged
+        let (_, far_end) = cycle_time::backgauge_setting(hit, length);
+        let towards_operator = if far_end { -1.0 } else { 1.0 };
+        let overhang = towards_operator * ((cog[0] - point[0]) * heading.cos() + (cog[1] - point[1]) * heading.sin());
+        let bent_overhang = before.iter().any(|h| if far_end { h.position_mm < hit.position_mm } else { h.position_mm > hit.position_mm });
+        steps.push(StepBalance {
+            sequence_order: hit.sequence_order,
+            cog_mm: cog,
+            overhang_mm: overhang,
+            tips_away: bent_overhang && overhang > shoulder,
+        });
+    }
+
+    let cog_mm = geometry::profile_centroid(&geometry::profile_polyline(job, &hits)).unwrap_or([length / 2.0, 0.0]);
+    PartHandling { mass_kg: physics::sheet_weight_kg(setup.sheet, setup.material), cog_mm, steps }
+}
+
+// --- Flange Lift ---
+
+/// Who or what holds the front flange while it lifts, least first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PartSupport {
+    Operator,
+    SecondOperator,
+    SheetFollower,
+}
+
+impl fmt::Display for PartSupport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartSupport::Operator => write!(f, "Operator"),
+            PartSupport::SecondOperator => write!(f, "Second operator"),
+            PartSupport::SheetFollower => write!(f, "Sheet follower"),
+        }
+    }
+}
+
+/// How the flange on the operator side moves during one job step's first hit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlangeLift {
+    pub sequence_order: usize,
+    pub flange_mm: f64,     // Flat length between the bend line and the operator's end of the sheet
+    pub flange_mass_kg: f64,
+    pub moment_nm: f64,     // Weight of the flange about the bend line, held at the start of the hit
+    pub tip_speed_m_s: f64, // Of the flange point furthest from the bend line, as the punch starts forming
+    pub support: PartSupport,
+}
+
+/// Points spread evenly along the sheet between two flat positions, in the
+/// profile after `hits`.
+fn section_points(job: &Job, hits: &[BendStep], from_mm: f64, to_mm: f64) -> Vec<[f64; 2]> {
+    (0..FLANGE_SAMPLES)
+        .map(|i| from_mm + (to_mm - from_mm) * (i as f64 + 0.5) / FLANGE_SAMPLES as f64)
+        .map(|position| geometry::profile_point_at(job, hits, position).0)
+        .collect()
+}
+
+/// Lift of the front flange at every step. In air bending the flanges turn
+/// about the bend line at `bending speed / (V/2)` rad/s when the punch starts to
+/// form, so long flanges whip up fast; heavy ones also load whoever holds them.
+pub fn flange_lifts(setup: &MachineSetup, job: &Job, speeds: &MachineSpeeds) -> Vec<FlangeLift> {
+    let hits = geometry::expand_machine_steps(job);
+    let length = job.sheet.original_length_mm;
+    let mass_per_mm = physics::sheet_weight_kg(setup.sheet, setup.material) / length.max(1e-9);
+    let angular_speed = speeds.bending_mm_s / (setup.die.v_opening_mm / 2.0); // rad/s
+    let mut lifts: Vec<FlangeLift> = Vec::new();
+
+    for (i, hit) in hits.iter().enumerate() {
+        if lifts.last().is_some_and(|l| l.sequence_order == hit.sequence_order) {
+            continue;
+        }
+        let before = &hits[..i];
+        let (_, far_end) = cycle_time::backgauge_setting(hit, length);
+        let (from, to) = if far_end { (0.0, hit.position_mm) } else { (hit.position_mm, length) };
+        let (point, heading) = geometry::profile_point_at(job, before, hit.position_mm);
+        let towards_operator = if far_end { -1.0 } else { 1.0 };
+        let (cos, sin) = (towards_operator * heading.cos(), towards_operator * heading.sin());
+
+        let points = section_points(job, before, from, to);
+        let arm_mm = points.iter().map(|[x, y]| (x - point[0]) * cos + (y - point[1]) * sin).sum::<f64>() / points.len() as f64;
+        let reach_mm = points.iter().map(|[x, y]| (x - point[0]).hypot(y - point[1])).fold(0.0, f64::max);
+
+        let flange_mm = to - from;
+        let flange_mass_kg = flange_mm * mass_per_mm;
+        let moment_nm = flange_mass_kg * GRAVITY_M_S2 * arm_mm.max(0.0) / 1000.0;
+        let tip_speed_m_s = angular_speed * reach_mm / 1000.0;
+        let support = if tip_speed_m_s > SHEET_FOLLOWER_TIP_SPEED_M_S || moment_nm > SHEET_FOLLOWER_MOMENT_NM {
+            PartSupport::SheetFollower
+        } else if tip_speed_m_s > SECOND_OPERATOR_TIP_SPEED_M_S || moment_nm > SECOND_OPERATOR_MOMENT_NM {
+            PartSupport::SecondOperator
+        } else {
+            PartSupport::Operator
+        };
+        lifts.push(FlangeLift { sequence_order: hit.sequence_order, flange_mm, flange_mass_kg, moment_nm, tip_speed_m_s, support });
+    }
+    lifts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{self, BendDirection, BendFeature, BendMethod, BendStep, Die, MaterialName, SheetMetal};
+
+    fn bend(sequence_order: usize, position_mm: f64) -> BendStep {
+        BendStep {
+            sequence_order,
+            position_mm,
+            target_angle_deg: 90.0,
+            radius_mm: 2.0,
+            direction: BendDirection::Up,
+            method: BendMethod::AirBending,
+            feature: BendFeature::Single,
+        }
+    }
+
+    fn handling(job: &Job) -> PartHandling {
+        let materials = model::default_materials();
+        let punches = model::default_punches();
+        let die = Die { name: "V16".to_string(), v_opening_mm: 16.0, angle_deg: 88.0, shoulder_radius_mm: 2.0 };
+        let setup = MachineSetup { sheet: &job.sheet, material: &materials[&MaterialName::Steel], punch: &punches[0], die: &die };
+        analyse(&setup, job)
+    }
+
+    #[test]
+    fn flat_sheet_balances_in_the_middle() {
+        let job = Job {
+            sheet: SheetMetal { original_length_mm: 1000.0, width_mm: 1000.0, thickness_mm: 1.0, ..SheetMetal::default() },
+            steps: Vec::new(),
+            ..Job::default()
+        };
+        let part = handling(&job);
+        assert!((part.mass_kg - 7.85).abs() < 1e-9);
+        assert!((part.cog_mm[0] - 500.0).abs() < 1e-9 && part.cog_mm[1].abs() < 1e-9);
+        assert!(part.steps.is_empty());
+        assert!(!part.over_handling_limit());
+    }
+
+    #[test]
+    fn symmetric_channel_balances_on_its_axis() {
+        let job = Job { steps: vec![bend(1, 100.0), bend(2, 200.0)], ..Job::default() };
+        let part = handling(&job);
+        let polyline = geometry::profile_polyline(&job, &geometry::expand_machine_steps(&job));
+        // Two 90° up bends turn the sheet back on itself: the channel opens
+        // towards the sheet start, symmetric about the middle of its web
+        let [_, min_y, max_x, max_y] = geometry::profile_bounds(&polyline).unwrap();
+        assert!((part.cog_mm[1] - (min_y + max_y) / 2.0).abs() < 1e-6, "{:?}", part.cog_mm);
+        assert!(part.cog_mm[0] > 50.0 && part.cog_mm[0] < max_x, "{:?}", part.cog_mm);
+        assert_eq!(part.steps.iter().map(|s| s.sequence_order).collect::<Vec<_>>(), vec![1, 2]);
+        // The first bend is made on the flat sheet: its overhang lies on the support arms
+        assert!(!part.steps[0].tips_away);
+    }
+
+    #[test]
+    fn heavy_blank_needs_two_operators() {
+        let job = Job {
+            sheet: SheetMetal { original_length_mm: 2000.0, width_mm: 1500.0, thickness_mm: 3.0, ..SheetMetal::default() },
+            steps: Vec::new(),
+            ..Job::default()
+        };
+        assert!(handling(&job).over_handling_limit());
+    }
+
+    fn lifts(sheet: SheetMetal, steps: Vec<BendStep>) -> Vec<FlangeLift> {
+        let materials = model::default_materials();
+        let punches = model::default_punches();
+        let die = Die { name: "V16".to_string(), v_opening_mm: 16.0, angle_deg: 88.0, shoulder_radius_mm: 2.0 };
+        let job = Job { sheet, steps, ..Job::default() };
+        let setup = MachineSetup { sheet: &job.sheet, material: &materials[&MaterialName::Steel], punch: &punches[0], die: &die };
+        flange_lifts(&setup, &job, &MachineSpeeds::default())
+    }
+
+    #[test]
+    fn short_light_flange_is_held_by_the_operator() {
+        let sheet = SheetMetal { original_length_mm: 100.0, width_mm: 50.0, thickness_mm: 1.0, ..SheetMetal::default() };
+        let lifts = lifts(sheet, vec![bend(1, 40.0)]);
+        assert_eq!(lifts.len(), 1);
+        // The 40 mm side is gauged, the 60 mm side lifts towards the operator
+        assert!((lifts[0].flange_mm - 60.0).abs() < 1e-9);
+        assert!(lifts[0].tip_speed_m_s < SECOND_OPERATOR_TIP_SPEED_M_S);
+        assert_eq!(lifts[0].support, PartSupport::Operator);
+    }
+
+    #[test]
+    fn long_narrow_flange_whips_up_too_fast_for_one_operator() {
+        let sheet = SheetMetal { original_length_mm: 1200.0, width_mm: 50.0, thickness_mm: 1.0, ..SheetMetal::default() };
+        let lift = &lifts(sheet, vec![bend(1, 600.0)])[0];
+        // 600 mm at 10 mm/s / 8 mm = 1.25 rad/s, the reach sampled to within 1/64 of the flange
+        assert!((lift.tip_speed_m_s - 0.75).abs() < 0.75 / FLANGE_SAMPLES as f64, "{}", lift.tip_speed_m_s);
+        assert!(lift.moment_nm < SECOND_OPERATOR_MOMENT_NM);
+        assert_eq!(lift.support, PartSupport::SecondOperator);
+    }
+
+    #[test]
+    fn long_heavy_flange_needs_a_sheet_follower() {
+        let sheet = SheetMetal { original_length_mm: 2000.0, width_mm: 1500.0, thickness_mm: 3.0, ..SheetMetal::default() };
+        let lift = &lifts(sheet, vec![bend(1, 1000.0)])[0];
+        assert!((lift.flange_mass_kg - 35.325).abs() < 1e-9);
+        // Half the blank's weight with its centre 500 mm out
+        assert!((lift.moment_nm - 35.325 * 9.81 * 0.5).abs() < 0.01, "{}", lift.moment_nm);
+        assert_eq!(lift.support, PartSupport::SheetFollower);
+    }
+}
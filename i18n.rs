@@ -0,0 +1,642 @@
+// src/i18n.rs
+// Translation catalog for the GUI. Keys are looked up per language; typed
+// library results (validation errors, tooling reasons, ...) get their own
+// functions so every variant is translated, not just its English Display text.
+use crate::numeric_input::{NumericInput, NumericInputError, Quantity};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::sync::OnceLock;
+use vouwbank::db::JobStorageError;
+use vouwbank::handling::PartSupport;
+use vouwbank::model::{BendDirection, BendFeature, BendFeatureKind, BendMethod, HemStyle, MaterialName, RollingDirection};
+use vouwbank::nesting::NestError;
+use vouwbank::physics;
+use vouwbank::production::{ProductionError, Shift};
+use vouwbank::tooling::RecommendationReason;
+use vouwbank::units::LengthUnit;
+use vouwbank::validation::{self, SheetDimension, ValidationError, ValidationWarning};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    Dutch,
+    English,
+    German, // Used at the sister plant
+}
+
+impl Language {
+    pub fn all() -> Vec<Self> {
+        vec![Language::Dutch, Language::English, Language::German]
+    }
+
+    fn column(self) -> usize {
+        match self {
+            Language::Dutch => 0,
+            Language::English => 1,
+            Language::German => 2,
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::Dutch => write!(f, "Nederlands"),
+            Language::English => write!(f, "English"),
+            Language::German => write!(f, "Deutsch"),
+        }
+    }
+}
+
+// --- Catalog ---
+
+/// key, [Dutch, English, German]. `{}` placeholders are filled in order by `trf`.
+const CATALOG: &[(&str, [&str; 3])] = &[
+    // Menu and window
+    ("menu.file", ["Bestand", "File", "Datei"]),
+    ("menu.new", ["Nieuwe Taak", "New Job", "Neuer Auftrag"]),
+    ("menu.open", ["Taak Openen...", "Open Job...", "Auftrag öffnen..."]),
+    ("menu.recent", ["Recente Bestanden", "Recent Files", "Zuletzt geöffnet"]),
+    ("menu.recent_empty", ["Geen recente bestanden", "No recent files", "Keine zuletzt geöffneten Dateien"]),
+    ("menu.recent_clear", ["Lijst wissen", "Clear list", "Liste leeren"]),
+    ("menu.library", ["Taakbibliotheek", "Job Library", "Auftragsbibliothek"]),
+    ("menu.save", ["Opslaan", "Save", "Speichern"]),
+    ("menu.save_as", ["Opslaan Als...", "Save As...", "Speichern unter..."]),
+    ("menu.exit", ["Afsluiten", "Exit", "Beenden"]),
+    ("menu.close", ["Taak sluiten", "Close Job", "Auftrag schließen"]),
+    ("menu.edit", ["Bewerken", "Edit", "Bearbeiten"]),
+    ("menu.undo", ["Ongedaan maken", "Undo", "Rückgängig"]),
+    ("menu.redo", ["Opnieuw", "Redo", "Wiederholen"]),
+    ("menu.copy_steps", ["Kopieer Buigstappen", "Copy Bend Steps", "Biegeschritte kopieren"]),
+    ("menu.paste_steps", ["Plak Buigstappen ({})", "Paste Bend Steps ({})", "Biegeschritte einfügen ({})"]),
+    ("menu.production", ["Productie", "Production", "Produktion"]),
+    ("menu.quote", ["Offerte", "Quote", "Angebot"]),
+    ("menu.nesting", ["Nesten", "Nesting", "Verschachtelung"]),
+    ("menu.settings", ["Instellingen", "Settings", "Einstellungen"]),
+    ("menu.language", ["Taal", "Language", "Sprache"]),
+    ("menu.units", ["Eenheden", "Units", "Einheiten"]),
+    ("menu.machine_speeds", ["Machinesnelheden...", "Machine Speeds...", "Maschinengeschwindigkeiten..."]),
+    ("menu.reset", ["Standaardinstellingen herstellen", "Reset to Defaults", "Standardeinstellungen wiederherstellen"]),
+    ("session.restored", ["Vorige sessie hersteld met taak '{}'.", "Restored the previous session with job '{}'.", "Vorherige Sitzung mit Auftrag '{}' wiederhergestellt."]),
+    ("session.restore_failed", ["Laatste taak kon niet worden hersteld: {}", "Could not restore the last job: {}", "Letzter Auftrag konnte nicht wiederhergestellt werden: {}"]),
+    ("session.reset", ["Instellingen teruggezet naar standaard.", "Settings reset to defaults.", "Einstellungen auf Standard zurückgesetzt."]),
+    ("app.version", ["CNC Plooibank Sim v0.1 ({})", "CNC Press Brake Sim v0.1 ({})", "CNC-Abkantpresse Sim v0.1 ({})"]),
+    ("app.initialized", ["Systeem geïnitialiseerd.", "System initialized.", "System initialisiert."]),
+    ("app.untitled", ["Naamloos", "Untitled", "Unbenannt"]),
+    ("main.setup", ["Taak & Machine Setup", "Job & Machine Setup", "Auftrag & Maschineneinrichtung"]),
+    ("main.control", ["Bediening & Uitvoer", "Control & Output", "Bedienung & Ausgabe"]),
+    ("common.na", ["n.v.t.", "N/A", "k. A."]),
+    ("common.cancel", ["Annuleren", "Cancel", "Abbrechen"]),
+    ("common.ok", ["OK", "OK", "OK"]),
+    ("common.search", ["Zoeken", "Search", "Suchen"]),
+    ("common.clear", ["Wissen", "Clear", "Löschen"]),
+    // Sheet
+    ("sheet.title", ["Plaateigenschappen", "Sheet Properties", "Blecheigenschaften"]),
+    ("sheet.length", ["Lengte ({}):", "Length ({}):", "Länge ({}):"]),
+    ("sheet.thickness", ["Dikte ({} of ga):", "Thickness ({} or ga):", "Dicke ({} oder ga):"]),
+    ("sheet.thickness_hint", ["bv. 1.5 of 16ga", "e.g. 1.5 or 16ga", "z. B. 1.5 oder 16ga"]),
+    ("sheet.gauge", ["≈ {} ga ({})", "≈ {} ga ({})", "≈ {} ga ({})"]),
+    ("sheet.unknown_gauge", ["Gauge {} staat niet in de {}-tabel voor dit materiaal.", "Gauge {} is not in the {} table for this material.", "Gauge {} fehlt in der {}-Tabelle für diesen Werkstoff."]),
+    ("sheet.width", ["Breedte ({}):", "Width ({}):", "Breite ({}):"]),
+    ("sheet.material", ["Materiaal:", "Material:", "Werkstoff:"]),
+    ("sheet.rolling", ["Walsrichting:", "Rolling direction:", "Walzrichtung:"]),
+    ("sheet.rolling_hint", ["Buiglijnen lopen over de breedte van de plaat.", "Bend lines run along the sheet width.", "Biegelinien verlaufen entlang der Blechbreite."]),
+    ("sheet.rolling_changed", ["Walsrichting ingesteld op: {}", "Rolling direction set to: {}", "Walzrichtung gesetzt auf: {}"]),
+    ("rolling.unknown", ["Onbekend", "Unknown", "Unbekannt"]),
+    ("rolling.along_length", ["Langs de lengte", "Along length", "Längs der Länge"]),
+    ("rolling.along_width", ["Langs de breedte", "Along width", "Längs der Breite"]),
+    ("sheet.update", ["Plaateigenschappen Bijwerken", "Update Sheet Properties", "Blecheigenschaften übernehmen"]),
+    ("sheet.min_radius", ["Aanbevolen min. buigradius: {}", "Recommended min. bend radius: {}", "Empfohlener min. Biegeradius: {}"]),
+    ("sheet.updated", ["Plaateigenschappen bijgewerkt. Buigstappen gewist.", "Sheet properties updated. Bend steps cleared.", "Blecheigenschaften übernommen. Biegeschritte gelöscht."]),
+    ("dimension.length", ["Lengte", "Length", "Länge"]),
+    ("dimension.thickness", ["Dikte", "Thickness", "Dicke"]),
+    ("dimension.width", ["Breedte", "Width", "Breite"]),
+    // Tooling
+    ("tooling.title", ["Gereedschap", "Tooling Setup", "Werkzeuge"]),
+    ("tooling.punch", ["Stempel:", "Punch:", "Oberwerkzeug:"]),
+    ("tooling.die", ["Matrijs:", "Die:", "Unterwerkzeug:"]),
+    ("tooling.selected_punch", ["Stempel: {} (hoek: {}°, radius: {})", "Selected punch: {} (angle: {}°, radius: {})", "Oberwerkzeug: {} (Winkel: {}°, Radius: {})"]),
+    ("tooling.selected_die", ["Matrijs: {} (V-opening: {}, hoek: {}°)", "Selected die: {} (V opening: {}, angle: {}°)", "Unterwerkzeug: {} (V-Öffnung: {}, Winkel: {}°)"]),
+    ("tooling.min_flange", ["Min. flenslengte: {}", "Min. flange length: {}", "Min. Schenkellänge: {}"]),
+    ("tooling.ideal_v", ["Ideale V-opening (8·t): {}", "Ideal V opening (8·t): {}", "Ideale V-Öffnung (8·t): {}"]),
+    ("tooling.recommend", ["Gereedschap Aanbevelen", "Recommend Tooling", "Werkzeug empfehlen"]),
+    ("tooling.use", ["Gebruik", "Use", "Verwenden"]),
+    ("tooling.flange_warning", ["Stap {}: flens {} < min. {} ({})", "Step {}: flange {} < min. {} ({})", "Schritt {}: Schenkel {} < min. {} ({})"]),
+    ("tooling.try_die", ["probeer {}", "try {}", "versuche {}"]),
+    ("tooling.no_suitable_die", ["geen geschikte matrijs", "no suitable die", "kein passendes Unterwerkzeug"]),
+    ("tooling.need_radius_angle", ["Voer een geldige buigradius en hoek in om gereedschap aan te bevelen.", "Enter a valid bend radius and angle to recommend tooling.", "Gültigen Biegeradius und Winkel eingeben, um Werkzeuge zu empfehlen."]),
+    ("tooling.recommended", ["Aanbevolen: {} / {}", "Recommended: {} / {}", "Empfohlen: {} / {}"]),
+    ("tooling.none_suitable", ["Geen geschikte stempel/matrijs-combinatie in de gereedschapsbibliotheek.", "No suitable punch/die combination in the tooling library.", "Keine passende Ober-/Unterwerkzeug-Kombination in der Werkzeugbibliothek."]),
+    ("tooling.updated", ["Gereedschapskeuze bijgewerkt.", "Tooling selection updated.", "Werkzeugauswahl aktualisiert."]),
+    ("tooling.none_selected", ["Geen stempel/matrijs geselecteerd.", "No punch/die selected.", "Kein Ober-/Unterwerkzeug ausgewählt."]),
+    ("tooling.flange_try_die", ["Probeer matrijs {} (V{}).", "Try die {} (V{}).", "Versuche Unterwerkzeug {} (V{})."]),
+    ("tooling.flange_no_die", ["Geen matrijs in de bibliotheek ondersteunt deze flens.", "No die in the tooling library supports this flange.", "Kein Unterwerkzeug in der Bibliothek unterstützt diesen Schenkel."]),
+    // Bend definition
+    ("bend.title", ["Buigstap Definiëren", "Define Bend Step", "Biegeschritt definieren"]),
+    ("bend.position", ["Positie ({}):", "Position ({}):", "Position ({}):"]),
+    ("bend.angle", ["Gewenste Hoek (°):", "Target Angle (°):", "Sollwinkel (°):"]),
+    ("bend.radius", ["Binnenradius ({}):", "Inner Radius ({}):", "Innenradius ({}):"]),
+    ("bend.direction", ["Richting:", "Direction:", "Richtung:"]),
+    ("bend.feature", ["Vorm:", "Feature:", "Form:"]),
+    ("bend.joggle_offset", ["Verspringing ({}):", "Joggle Offset ({}):", "Kröpfungshöhe ({}):"]),
+    ("bend.method", ["Methode:", "Method:", "Verfahren:"]),
+    ("bend.add", ["Voeg Buiging Toe Aan De Taak", "Add Bend to Job", "Biegung zum Auftrag hinzufügen"]),
+    ("bend.field_position", ["buigpositie", "bend position", "Biegeposition"]),
+    ("bend.field_angle", ["buighoek", "bend angle", "Biegewinkel"]),
+    ("bend.field_radius", ["buigradius", "bend radius", "Biegeradius"]),
+    ("bend.field_joggle_offset", ["verspringing", "joggle offset", "Kröpfungshöhe"]),
+    ("bend.added", ["Buigstap {} toegevoegd.", "Bend step {} added.", "Biegeschritt {} hinzugefügt."]),
+    // Input fields
+    ("input.empty", ["Vul een waarde in.", "Enter a value.", "Wert eingeben."]),
+    ("input.invalid", ["Ongeldige invoer bij teken {}.", "Invalid input at character {}.", "Ungültige Eingabe bei Zeichen {}."]),
+    ("input.division_by_zero", ["Deling door nul.", "Division by zero.", "Division durch null."]),
+    ("input.out_of_range", ["Buiten bereik ({} - {}).", "Out of range ({} - {}).", "Außerhalb des Bereichs ({} - {})."]),
+    ("input.field_error", ["{}: {}", "{}: {}", "{}: {}"]),
+    ("input.evaluated", ["= {}", "= {}", "= {}"]),
+    // Sequence
+    ("sequence.title", ["Buigvolgorde Huidige Taak ({})", "Current Job Bend Sequence ({})", "Biegefolge aktueller Auftrag ({})"]),
+    ("sequence.empty", ["Geen buigstappen gedefinieerd voor de huidige taak.", "No bend steps defined for the current job.", "Keine Biegeschritte für den aktuellen Auftrag definiert."]),
+    ("sequence.col_position", ["Pos ({})", "Pos ({})", "Pos ({})"]),
+    ("sequence.col_angle", ["Hoek", "Angle", "Winkel"]),
+    ("sequence.col_radius", ["Radius ({})", "Radius ({})", "Radius ({})"]),
+    ("sequence.col_direction", ["Richting", "Dir", "Richtung"]),
+    ("sequence.col_method", ["Methode", "Method", "Verfahren"]),
+    ("sequence.col_feature", ["Vorm", "Feature", "Form"]),
+    ("sequence.col_support", ["Ondersteuning", "Support", "Abstützung"]),
+    ("support.operator", ["Operator", "Operator", "Bediener"]),
+    ("support.second_operator", ["Tweede operator", "Second operator", "Zweiter Bediener"]),
+    ("support.sheet_follower", ["Plaatvolger", "Sheet follower", "Blechfolger"]),
+    ("support.detail", ["Voorste flens {} ({} kg): opwaartse snelheid {} m/s, moment {} N·m", "Front flange {} ({} kg): lift speed {} m/s, moment {} N·m", "Vorderer Schenkel {} ({} kg): Hubgeschwindigkeit {} m/s, Moment {} N·m"]),
+    ("sequence.select_hint", ["Klik om te selecteren voor kopiëren; zonder selectie worden alle stappen gekopieerd.", "Click to select for copying; without a selection all steps are copied.", "Zum Kopieren auswählen; ohne Auswahl werden alle Schritte kopiert."]),
+    ("sequence.clear", ["Wis Alle Buigstappen", "Clear All Bend Steps", "Alle Biegeschritte löschen"]),
+    ("sequence.nothing_to_clear", ["Geen buigstappen om te wissen.", "No bend steps to clear.", "Keine Biegeschritte zum Löschen."]),
+    ("sequence.cleared", ["Alle buigstappen gewist.", "All bend steps cleared.", "Alle Biegeschritte gelöscht."]),
+    // Execution and profile
+    ("exec.title", ["Machine Bediening", "Machine Control", "Maschinensteuerung"]),
+    ("exec.run", ["Voer Simulatie Uit & Genereer Profiel", "Run Simulation & Generate Profile", "Simulation starten & Profil erzeugen"]),
+    ("exec.status", ["Machinestatus: {}", "Machine status: {}", "Maschinenstatus: {}"]),
+    ("exec.parts_bent", ["Gebogen onderdelen deze ploeg ({}): {}", "Parts bent this shift ({}): {}", "Gebogene Teile diese Schicht ({}): {}"]),
+    ("exec.ready", ["Gereed", "Ready", "Bereit"]),
+    ("exec.simulating", ["Simulatie van {} buigstappen ({} slagen) voor taak '{}'...", "Simulating {} bend steps ({} hits) for job '{}'...", "Simuliere {} Biegeschritte ({} Hübe) für Auftrag '{}'..."]),
+    ("exec.simulating_hit", ["  Slag stap {}: pos {}, hoek {}, radius {}, {}, {}", "  Hit step {}: pos {}, angle {}, radius {}, {}, {}", "  Hub Schritt {}: Pos {}, Winkel {}, Radius {}, {}, {}"]),
+    ("exec.complete", ["Simulatie compleet.", "Simulation complete.", "Simulation abgeschlossen."]),
+    ("cycle.total", ["Geschatte cyclustijd: {} per onderdeel", "Estimated cycle time: {} per part", "Geschätzte Taktzeit: {} pro Teil"]),
+    ("cycle.details", ["Cyclustijd per stap", "Cycle time per step", "Taktzeit pro Schritt"]),
+    ("cycle.col_step", ["Stap", "Step", "Schritt"]),
+    ("cycle.col_hits", ["Slagen", "Hits", "Hübe"]),
+    ("cycle.col_backgauge", ["Aanslag (s)", "Backgauge (s)", "Anschlag (s)"]),
+    ("cycle.col_handling", ["Hanteren (s)", "Handling (s)", "Handling (s)"]),
+    ("cycle.col_tools", ["Gereedschap (s)", "Tools (s)", "Werkzeug (s)"]),
+    ("cycle.col_ram", ["Bovenbalk (s)", "Ram (s)", "Oberwange (s)"]),
+    ("cycle.col_total", ["Totaal (s)", "Total (s)", "Gesamt (s)"]),
+    ("cycle.summary", ["Laden/lossen {}, {}× omdraaien, {}× keren, {}× gereedschapswissel.", "Load/unload {}, {} flip(s), {} rotation(s), {} tool change(s).", "Be-/Entladen {}, {}× wenden, {}× drehen, {}× Werkzeugwechsel."]),
+    ("speeds.title", ["Machinesnelheden", "Machine Speeds", "Maschinengeschwindigkeiten"]),
+    ("speeds.approach", ["Ijlgang omlaag (mm/s):", "Approach (mm/s):", "Eilgang abwärts (mm/s):"]),
+    ("speeds.bending", ["Buigsnelheid (mm/s):", "Bending (mm/s):", "Biegegeschwindigkeit (mm/s):"]),
+    ("speeds.return", ["Ijlgang omhoog (mm/s):", "Return (mm/s):", "Eilgang aufwärts (mm/s):"]),
+    ("speeds.opening", ["Opening boven plaat (mm):", "Opening above sheet (mm):", "Öffnung über Blech (mm):"]),
+    ("speeds.dwell", ["Druk houden (s):", "Dwell (s):", "Druckhaltezeit (s):"]),
+    ("speeds.backgauge", ["Achteraanslag (mm/s):", "Backgauge (mm/s):", "Hinteranschlag (mm/s):"]),
+    ("speeds.backgauge_settle", ["Aanslag insteltijd (s):", "Backgauge settle (s):", "Anschlag Einschwingzeit (s):"]),
+    ("speeds.tool_change", ["Gereedschapswissel (s):", "Tool change (s):", "Werkzeugwechsel (s):"]),
+    ("speeds.flip", ["Omdraaien (s):", "Flip (s):", "Wenden (s):"]),
+    ("speeds.rotate", ["Keren (s):", "Rotate (s):", "Drehen (s):"]),
+    ("speeds.load_unload", ["Laden/lossen (s):", "Load/unload (s):", "Be-/Entladen (s):"]),
+    ("speeds.defaults", ["Standaardwaarden", "Defaults", "Standardwerte"]),
+    ("handling.mass", ["Gewicht onderdeel: {} kg", "Part weight: {} kg", "Teilegewicht: {} kg"]),
+    ("handling.over_limit", ["Zwaarder dan {} kg: met twee personen of een hefhulp hanteren.", "Heavier than {} kg: handle with two people or a lifting aid.", "Schwerer als {} kg: zu zweit oder mit Hebehilfe handhaben."]),
+    ("handling.details", ["Zwaartepunt per stap", "Centre of gravity per step", "Schwerpunkt pro Schritt"]),
+    ("handling.col_step", ["Stap", "Step", "Schritt"]),
+    ("handling.col_cog", ["Zwaartepunt (x, y)", "CoG (x, y)", "Schwerpunkt (x, y)"]),
+    ("handling.col_overhang", ["Overhang naar operator", "Overhang to operator", "Überhang zum Bediener"]),
+    ("handling.tips_away", ["Kantelt weg van aanslag", "Tips away from backgauge", "Kippt vom Anschlag weg"]),
+    ("handling.balanced", ["Stabiel", "Stable", "Stabil"]),
+    ("handling.final_cog", ["Zwaartepunt eindprofiel: ({}, {})", "CoG of finished part: ({}, {})", "Schwerpunkt Fertigteil: ({}, {})"]),
+    ("profile.title", ["Gesimuleerd Plaatprofiel", "Simulated Sheet Profile", "Simuliertes Blechprofil"]),
+    ("profile.not_generated", ["Profiel niet gegenereerd.", "Profile not generated.", "Profil nicht erzeugt."]),
+    ("profile.generated", ["Gesimuleerd profiel gegenereerd.", "Simulated profile generated.", "Simuliertes Profil erzeugt."]),
+    ("profile.outdated_sheet", ["Profiel verouderd door plaatwijziging.", "Profile outdated due to sheet change.", "Profil veraltet wegen Blechänderung."]),
+    ("profile.outdated_bend", ["Profiel verouderd door nieuwe buiging.", "Profile outdated due to new bend.", "Profil veraltet wegen neuer Biegung."]),
+    ("profile.outdated_cleared", ["Profiel verouderd, buigingen gewist.", "Profile outdated, bends cleared.", "Profil veraltet, Biegungen gelöscht."]),
+    ("profile.outdated_undo", ["Profiel verouderd door ongedaan maken.", "Profile outdated by undo/redo.", "Profil veraltet durch Rückgängig/Wiederholen."]),
+    ("profile.outdated_loaded", ["Nieuwe taak geladen, profiel verouderd.", "New job loaded, profile outdated.", "Neuer Auftrag geladen, Profil veraltet."]),
+    // Files
+    ("file.filter_job", ["Vouwbank taak", "Press brake job", "Abkant-Auftrag"]),
+    ("file.save_cancelled", ["Opslaan geannuleerd.", "Save cancelled.", "Speichern abgebrochen."]),
+    ("file.load_cancelled", ["Laden geannuleerd.", "Load cancelled.", "Laden abgebrochen."]),
+    ("file.saved", ["Taak '{}' opgeslagen in '{}'.", "Job '{}' saved to '{}'.", "Auftrag '{}' gespeichert in '{}'."]),
+    ("file.loaded", ["Taak geladen uit '{}'.", "Job loaded from '{}'.", "Auftrag aus '{}' geladen."]),
+    ("file.save_failed", ["Opslaan mislukt", "Save failed", "Speichern fehlgeschlagen"]),
+    ("file.load_failed", ["Laden mislukt", "Load failed", "Laden fehlgeschlagen"]),
+    ("file.cause", ["oorzaak: {}", "cause: {}", "Ursache: {}"]),
+    ("file.new_job", ["Nieuwe taak aangemaakt.", "New job created.", "Neuer Auftrag angelegt."]),
+    ("file.action_cancelled", ["Actie geannuleerd.", "Action cancelled.", "Aktion abgebrochen."]),
+    ("file.closed", ["Taak '{}' gesloten.", "Closed job '{}'.", "Auftrag '{}' geschlossen."]),
+    ("file.already_open", ["{} is al geopend.", "{} is already open.", "{} ist bereits geöffnet."]),
+    ("quote.title", ["Offerte", "Quote", "Angebot"]),
+    ("quote.title_for", ["Offerte: {}", "Quote: {}", "Angebot: {}"]),
+    ("quote.quantity", ["Seriegrootte:", "Batch size:", "Losgröße:"]),
+    ("quote.machine_rate", ["Machinetarief per uur:", "Machine rate per hour:", "Maschinenstundensatz:"]),
+    ("quote.setup_minutes", ["Insteltijd (min):", "Setup time (min):", "Rüstzeit (min):"]),
+    ("quote.currency", ["Valuta:", "Currency:", "Währung:"]),
+    ("quote.price_per_kg", ["Prijs {} per kg:", "Price of {} per kg:", "Preis {} pro kg:"]),
+    ("quote.export_csv", ["Exporteer CSV", "Export CSV", "CSV exportieren"]),
+    ("quote.export_pdf", ["Exporteer PDF", "Export PDF", "PDF exportieren"]),
+    ("quote.filter_csv", ["CSV-bestanden", "CSV files", "CSV-Dateien"]),
+    ("quote.filter_pdf", ["PDF-bestanden", "PDF files", "PDF-Dateien"]),
+    ("quote.exported", ["Offerte geëxporteerd naar {}.", "Exported quote to {}.", "Angebot nach {} exportiert."]),
+    ("quote.export_failed", ["Exporteren offerte naar {} mislukt: {}", "Failed to export quote to {}: {}", "Export des Angebots nach {} fehlgeschlagen: {}"]),
+    ("quote.line_date", ["Datum: {}", "Date: {}", "Datum: {}"]),
+    ("quote.line_job", ["Taak: {}", "Job: {}", "Auftrag: {}"]),
+    ("quote.line_sheet", ["Plaat: {} {} × {}, t = {}", "Sheet: {} {} × {}, t = {}", "Blech: {} {} × {}, t = {}"]),
+    ("quote.line_bends", ["Buigstappen: {}", "Bend steps: {}", "Biegeschritte: {}"]),
+    ("quote.line_weight", ["Gewicht per onderdeel: {} kg à {}/kg", "Weight per part: {} kg at {}/kg", "Gewicht pro Teil: {} kg zu {}/kg"]),
+    ("quote.line_material", ["Materiaal per onderdeel: {}", "Material per part: {}", "Material pro Teil: {}"]),
+    ("quote.line_cycle", ["Cyclustijd: {} s à {}/uur", "Cycle time: {} s at {}/hour", "Taktzeit: {} s zu {}/Stunde"]),
+    ("quote.line_machine", ["Machinetijd per onderdeel: {}", "Machine time per part: {}", "Maschinenzeit pro Teil: {}"]),
+    ("quote.line_setup", ["Instellen ({} min) per serie: {}", "Setup ({} min) per batch: {}", "Rüsten ({} min) pro Los: {}"]),
+    ("quote.line_quantity", ["Aantal: {}", "Quantity: {}", "Menge: {}"]),
+    ("quote.line_unit_price", ["Stukprijs: {}", "Unit price: {}", "Stückpreis: {}"]),
+    ("quote.line_batch_total", ["Totaal serie: {}", "Batch total: {}", "Gesamt Los: {}"]),
+    ("nest.title", ["Nesten", "Nesting", "Verschachtelung"]),
+    ("nest.add_job", ["Voeg huidige taak toe", "Add current job", "Aktuellen Auftrag hinzufügen"]),
+    ("nest.quantity", ["Aantal:", "Quantity:", "Menge:"]),
+    ("nest.col_job", ["Taak", "Job", "Auftrag"]),
+    ("nest.col_stock", ["Materiaal", "Stock", "Material"]),
+    ("nest.col_blank", ["Uitslag ({})", "Blank ({})", "Zuschnitt ({})"]),
+    ("nest.col_quantity", ["Aantal", "Qty", "Menge"]),
+    ("nest.empty", ["Nog geen uitslagen toegevoegd.", "No blanks added yet.", "Noch keine Zuschnitte hinzugefügt."]),
+    ("nest.plate", ["Plaatformaat:", "Plate size:", "Tafelformat:"]),
+    ("nest.spacing", ["Tussenruimte (mm):", "Spacing (mm):", "Abstand (mm):"]),
+    ("nest.summary", ["{} platen, benutting {} %, restmateriaal {} m²", "{} plate(s), utilisation {} %, scrap {} m²", "{} Tafel(n), Ausnutzung {} %, Verschnitt {} m²"]),
+    ("nest.plate_line", ["Plaat {}: {} {}, {} delen, benutting {} %", "Plate {}: {} {}, {} parts, utilisation {} %", "Tafel {}: {} {}, {} Teile, Ausnutzung {} %"]),
+    ("nest.preview", ["Voorbeeld plaat:", "Preview plate:", "Vorschau Tafel:"]),
+    ("nest.export_dxf", ["Exporteer DXF", "Export DXF", "DXF exportieren"]),
+    ("nest.exported", ["Nestlayout geëxporteerd naar {}.", "Exported nest layout to {}.", "Schachtelplan nach {} exportiert."]),
+    ("nest.export_failed", ["Exporteren nestlayout naar {} mislukt: {}", "Failed to export nest layout to {}: {}", "Export des Schachtelplans nach {} fehlgeschlagen: {}"]),
+    ("nest.no_blanks", ["Geen uitslagen om te nesten.", "No blanks to nest.", "Keine Zuschnitte zum Verschachteln."]),
+    ("nest.too_large", ["Uitslag van '{}' past niet op een plaat van {}.", "Blank of '{}' does not fit on a {} plate.", "Zuschnitt von '{}' passt nicht auf eine Tafel {}."]),
+    ("production.title", ["Productiewachtrij", "Production Queue", "Produktionswarteschlange"]),
+    ("production.new_order", ["Nieuwe order voor '{}'", "New order for '{}'", "Neuer Auftrag für '{}'"]),
+    ("production.customer", ["Klant:", "Customer:", "Kunde:"]),
+    ("production.quantity", ["Aantal:", "Quantity:", "Menge:"]),
+    ("production.due_date", ["Leverdatum (JJJJ-MM-DD):", "Due date (YYYY-MM-DD):", "Liefertermin (JJJJ-MM-TT):"]),
+    ("production.add", ["Toevoegen aan Wachtrij", "Add to Queue", "Zur Warteschlange hinzufügen"]),
+    ("production.empty", ["Geen orders in de wachtrij.", "No orders in the queue.", "Keine Aufträge in der Warteschlange."]),
+    ("production.col_order", ["Order", "Order", "Auftrag"]),
+    ("production.col_customer", ["Klant", "Customer", "Kunde"]),
+    ("production.col_job", ["Taak", "Job", "Auftrag"]),
+    ("production.col_due", ["Leverdatum", "Due", "Termin"]),
+    ("production.col_progress", ["Gebogen", "Bent", "Gebogen"]),
+    ("production.mark_one", ["+1", "+1", "+1"]),
+    ("production.mark_one_hint", ["Eén onderdeel gebogen", "One part bent", "Ein Teil gebogen"]),
+    ("production.mark_rest", ["Rest", "Rest", "Rest"]),
+    ("production.mark_rest_hint", ["Alle resterende onderdelen gebogen", "All remaining parts bent", "Alle restlichen Teile gebogen"]),
+    ("production.open_job", ["Open taak", "Open job", "Auftrag öffnen"]),
+    ("production.remove", ["Verwijder", "Remove", "Entfernen"]),
+    ("production.overdue", ["Te laat", "Overdue", "Überfällig"]),
+    ("production.complete", ["Gereed", "Done", "Fertig"]),
+    ("production.shift_totals", ["Totalen per ploeg", "Shift totals", "Summen pro Schicht"]),
+    ("production.no_history", ["Nog geen onderdelen gebogen.", "No parts bent yet.", "Noch keine Teile gebogen."]),
+    ("production.shift_row", ["{} {}: {} onderdelen", "{} {}: {} parts", "{} {}: {} Teile"]),
+    ("production.invalid_quantity", ["Ongeldig aantal: '{}'", "Invalid quantity: '{}'", "Ungültige Menge: '{}'"]),
+    ("production.invalid_date", ["Ongeldige leverdatum: '{}' (JJJJ-MM-DD)", "Invalid due date: '{}' (YYYY-MM-DD)", "Ungültiger Liefertermin: '{}' (JJJJ-MM-TT)"]),
+    ("production.job_not_saved", ["Taak is nog niet opgeslagen; de order kan de taak niet openen.", "The job isn't saved yet; the order can't open it.", "Der Auftrag ist noch nicht gespeichert; die Bestellung kann ihn nicht öffnen."]),
+    ("production.order_added", ["Order {} toegevoegd: {} × '{}'.", "Added order {}: {} × '{}'.", "Auftrag {} hinzugefügt: {} × '{}'."]),
+    ("production.parts_marked", ["{} onderdeel/onderdelen gebogen voor order {}.", "Marked {} part(s) bent for order {}.", "{} Teil(e) für Auftrag {} als gebogen markiert."]),
+    ("production.order_complete", ["Order {} gereed: alle {} onderdelen gebogen.", "Order {} complete: all {} parts bent.", "Auftrag {} fertig: alle {} Teile gebogen."]),
+    ("production.order_removed", ["Order {} ('{}') verwijderd.", "Removed order {} ('{}').", "Auftrag {} ('{}') entfernt."]),
+    ("production.zero_quantity", ["Aantal moet minstens 1 zijn.", "Quantity must be at least 1.", "Menge muss mindestens 1 sein."]),
+    ("production.unknown_order", ["Order {} staat niet in de wachtrij.", "Order {} is not in the queue.", "Auftrag {} ist nicht in der Warteschlange."]),
+    ("production.exceeds_quantity", ["Order {} heeft nog maar {} onderdelen te gaan.", "Order {} has only {} parts left to bend.", "Auftrag {} hat nur noch {} Teile offen."]),
+    ("shift.early", ["Vroeg", "Early", "Früh"]),
+    ("shift.late", ["Laat", "Late", "Spät"]),
+    ("shift.night", ["Nacht", "Night", "Nacht"]),
+    ("edit.undone", ["Laatste wijziging ongedaan gemaakt.", "Undid the last change.", "Letzte Änderung rückgängig gemacht."]),
+    ("edit.redone", ["Wijziging opnieuw uitgevoerd.", "Redid the change.", "Änderung wiederholt."]),
+    ("edit.nothing_to_copy", ["Geen buigstappen om te kopiëren.", "No bend steps to copy.", "Keine Biegeschritte zum Kopieren."]),
+    ("edit.copied", ["{} buigstap(pen) gekopieerd uit '{}'.", "Copied {} bend step(s) from '{}'.", "{} Biegeschritt(e) aus '{}' kopiert."]),
+    ("edit.clipboard_empty", ["Geen gekopieerde buigstappen.", "No copied bend steps.", "Keine kopierten Biegeschritte."]),
+    ("edit.pasted", ["{} buigstap(pen) geplakt.", "Pasted {} bend step(s).", "{} Biegeschritt(e) eingefügt."]),
+    ("edit.paste_skipped", ["Gekopieerde stap {} overgeslagen: {}", "Skipped copied step {}: {}", "Kopierter Schritt {} übersprungen: {}"]),
+    ("file.logo_failed", ["Laden van het logo mislukt: {}", "Failed to load app logo: {}", "Logo konnte nicht geladen werden: {}"]),
+    ("assets.override_used", ["Afbeelding geladen uit {}.", "Loaded image from {}.", "Bild aus {} geladen."]),
+    ("assets.override_failed", ["{} niet bruikbaar, ingebouwde afbeelding gebruikt: {}", "Could not use {}, using the built-in image: {}", "{} nicht verwendbar, eingebautes Bild wird verwendet: {}"]),
+    ("unsaved.title", ["Niet-opgeslagen wijzigingen", "Unsaved Changes", "Ungespeicherte Änderungen"]),
+    ("unsaved.body", ["Taak '{}' heeft niet-opgeslagen wijzigingen. Eerst opslaan?", "Job '{}' has unsaved changes. Save first?", "Auftrag '{}' hat ungespeicherte Änderungen. Zuerst speichern?"]),
+    ("unsaved.body_many", ["{} taken hebben niet-opgeslagen wijzigingen. Alles opslaan?", "{} jobs have unsaved changes. Save all?", "{} Aufträge haben ungespeicherte Änderungen. Alle speichern?"]),
+    ("unsaved.discard", ["Niet opslaan", "Don't Save", "Nicht speichern"]),
+    // Job library
+    ("library.folder", ["Map:", "Folder:", "Ordner:"]),
+    ("library.browse", ["Bladeren...", "Browse...", "Durchsuchen..."]),
+    ("library.refresh", ["Vernieuwen", "Refresh", "Aktualisieren"]),
+    ("library.search", ["Zoeken:", "Search:", "Suchen:"]),
+    ("library.search_hint", ["naam of bestand", "name or file", "Name oder Datei"]),
+    ("library.all", ["Alle", "All", "Alle"]),
+    ("library.material", ["Materiaal", "Material", "Werkstoff"]),
+    ("library.skipped", ["{} bestand(en) overgeslagen (geen geldige taak).", "{} file(s) skipped (not a valid job).", "{} Datei(en) übersprungen (kein gültiger Auftrag)."]),
+    ("library.summary", ["{}, {}, {} buigingen", "{}, {}, {} bends", "{}, {}, {} Biegungen"]),
+    ("library.open", ["Openen", "Open", "Öffnen"]),
+    ("library.read_failed", ["Taakbibliotheek lezen mislukt", "Reading job library failed", "Auftragsbibliothek konnte nicht gelesen werden"]),
+    ("library.found", ["{} taken gevonden in '{}'.", "{} jobs found in '{}'.", "{} Aufträge in '{}' gefunden."]),
+    // Message log
+    ("log.toggle", ["Log ({})", "Log ({})", "Protokoll ({})"]),
+    ("log.title", ["Berichten", "Messages", "Meldungen"]),
+    ("log.export", ["Exporteren...", "Export...", "Exportieren..."]),
+    ("log.filter_text", ["Tekst", "Text", "Text"]),
+    ("log.exported", ["{} logregels geëxporteerd naar '{}'.", "{} log lines exported to '{}'.", "{} Protokollzeilen nach '{}' exportiert."]),
+    ("log.export_failed", ["Log exporteren naar '{}' mislukt: {}", "Exporting log to '{}' failed: {}", "Protokoll-Export nach '{}' fehlgeschlagen: {}"]),
+    // Model names
+    ("material.steel", ["Staal", "Steel", "Stahl"]),
+    ("material.aluminum", ["Aluminium", "Aluminum", "Aluminium"]),
+    ("material.stainless_steel", ["RVS", "Stainless Steel", "Edelstahl"]),
+    ("material.copper", ["Koper", "Copper", "Kupfer"]),
+    ("material.mild_steel", ["Zacht staal", "Mild Steel", "Baustahl"]),
+    ("direction.up", ["Omhoog", "Up", "Hoch"]),
+    ("direction.down", ["Omlaag", "Down", "Runter"]),
+    ("method.air", ["Vrijbuigen", "Air Bending", "Freibiegen"]),
+    ("method.bottoming", ["Nastoten", "Bottoming", "Prägebiegen"]),
+    ("method.coining", ["Munten", "Coining", "Prägen"]),
+    ("method.hemming", ["Zomen", "Hemming", "Falzen"]),
+    ("feature.single", ["Enkel", "Single", "Einzeln"]),
+    ("feature.hem_open", ["Zoom (open)", "Hem (open)", "Falz (offen)"]),
+    ("feature.hem_closed", ["Zoom (gesloten)", "Hem (closed)", "Falz (geschlossen)"]),
+    ("feature.hem_teardrop", ["Zoom (druppel)", "Hem (teardrop)", "Falz (Tropfen)"]),
+    ("feature.joggle", ["Verspringing", "Joggle", "Kröpfung"]),
+    ("feature.joggle_offset", ["Verspringing {}", "Joggle {}", "Kröpfung {}"]),
+    ("feature.bump", ["Bumpen", "Bump", "Schrittbiegen"]),
+    ("feature.bump_hits", ["Bumpen {}× {}", "Bump {}× {}", "Schrittbiegen {}× {}"]),
+    // Validation errors
+    ("error.sheet_dimension", ["{} buiten bereik ({}-{}).", "{} out of range ({}-{}).", "{} außerhalb des Bereichs ({}-{})."]),
+    ("error.position_outside", ["Buigpositie ({}) ligt buiten de plaatlengte (0-{}).", "Bend position ({}) is outside the sheet length (0-{}).", "Biegeposition ({}) liegt außerhalb der Blechlänge (0-{})."]),
+    ("error.angle_range", ["Buighoek ({}°) buiten bereik ({}-{}°).", "Bend angle ({}°) out of range ({}-{}°).", "Biegewinkel ({}°) außerhalb des Bereichs ({}-{}°)."]),
+    ("error.radius_range", ["Buigradius ({}) buiten bereik ({}-{}).", "Bend radius ({}) out of range ({}-{}).", "Biegeradius ({}) außerhalb des Bereichs ({}-{})."]),
+    ("error.joggle_offset", ["Ongeldige verspringing: {}", "Invalid joggle offset: {}", "Ungültige Kröpfungshöhe: {}"]),
+    ("error.hit_outside", ["Buiglijn ({}) van deze stap valt buiten de plaat (0-{}).", "Bend line ({}) of this step falls outside the sheet (0-{}).", "Biegelinie ({}) dieses Schritts liegt außerhalb des Blechs (0-{})."]),
+    ("error.cannot_air_bend", ["Gereedschap ({}°/{}°) kan niet vrijbuigen naar {}°.", "Tools ({}°/{}°) cannot air bend to {}°.", "Werkzeuge ({}°/{}°) können nicht auf {}° freibiegen."]),
+    ("error.tool_angle_mismatch", ["{} vraagt gereedschap van {}° (stempel {}°, matrijs {}°).", "{} needs tools matching {}° (punch {}°, die {}°).", "{} erfordert Werkzeuge mit {}° (Oberwerkzeug {}°, Unterwerkzeug {}°)."]),
+    ("error.hem_not_flattened", ["Zomen drukt de flens plat; doelhoek moet ≤ {}° zijn.", "Hemming flattens the flange; target angle must be ≤ {}°.", "Falzen drückt den Schenkel flach; Sollwinkel muss ≤ {}° sein."]),
+    ("error.missing_prebend", ["Zomen op {} vraagt eerst een voorbuiging tot ≤ {}° op dezelfde positie.", "Hemming at {} needs a pre-bend step to ≤ {}° at the same position.", "Falzen bei {} erfordert eine Vorbiegung auf ≤ {}° an derselben Position."]),
+    ("error.force_capacity", ["{} vraagt ≈ {}kN, perscapaciteit is {}kN.", "{} needs ≈ {}kN, press capacity is {}kN.", "{} erfordert ≈ {}kN, Presskraft ist {}kN."]),
+    ("error.no_steps", ["Geen buigstappen om te simuleren.", "No bend steps to simulate.", "Keine Biegeschritte zum Simulieren."]),
+    ("error.in_step", ["Stap {}: {}", "Step {}: {}", "Schritt {}: {}"]),
+    // Validation warnings
+    ("warning.radius_below_min", ["Waarschuwing: radius {} < aanbevolen minimum {} voor dit materiaal.", "Warning: radius {} < recommended min {} for material.", "Warnung: Radius {} < empfohlenes Minimum {} für den Werkstoff."]),
+    ("warning.radius_mismatch", ["Waarschuwing: stap {}: {} met {} / {} vormt ≈ {} radius, niet {}.", "Warning: step {}: {} with {} / {} forms ≈ {} radius, not {}.", "Warnung: Schritt {}: {} mit {} / {} ergibt ≈ {} Radius, nicht {}."]),
+    ("warning.short_flanges", ["Waarschuwing: flens {} te kort voor gekozen matrijs (min. {}) bij stap(pen) {}.", "Warning: flange {} too short for selected die (min {}) at step(s) {}.", "Warnung: Schenkel {} zu kurz für gewähltes Unterwerkzeug (min. {}) bei Schritt(en) {}."]),
+    ("warning.converted_to_bump", ["Radius {} te groot voor één slag; omgezet naar {}.", "Radius {} too large for a single hit; converted to {}.", "Radius {} zu groß für einen Hub; umgewandelt in {}."]),
+    ("warning.along_rolling", ["Waarschuwing: stap {}: buiglijn langs de walsrichting, radius {} < min. {}; kans op scheuren.", "Warning: step {}: bend line along the rolling direction, radius {} < min {}; risk of cracking.", "Warnung: Schritt {}: Biegelinie längs der Walzrichtung, Radius {} < min. {}; Rissgefahr."]),
+    ("warning.heavy_part", ["Waarschuwing: onderdeel weegt {} kg, meer dan één operator mag hanteren ({} kg).", "Warning: part weighs {} kg, more than one operator should handle ({} kg).", "Warnung: Teil wiegt {} kg, mehr als ein Bediener handhaben sollte ({} kg)."]),
+    ("warning.tips_away", ["Waarschuwing: onderdeel kantelt weg van de aanslag bij stap(pen) {}; ondersteun het tijdens het buigen.", "Warning: part tips away from the backgauge at step(s) {}; support it while bending.", "Warnung: Teil kippt bei Schritt(en) {} vom Anschlag weg; beim Biegen abstützen."]),
+    // Tooling reasons
+    ("reason.v_ratio_ok", ["V{} is {}·t (ideaal {}·t)", "V{} is {}·t (ideal {}·t)", "V{} ist {}·t (ideal {}·t)"]),
+    ("reason.v_ratio_out", ["V{} is {}·t, buiten het bereik 6-12·t", "V{} is {}·t, outside the 6-12·t range", "V{} ist {}·t, außerhalb von 6-12·t"]),
+    ("reason.radius", ["Radius ≈ {} (gevraagd {})", "Radius ≈ {} (requested {})", "Radius ≈ {} (gefordert {})"]),
+    ("reason.punch_radius", ["Stempelradius {} is groter dan de gevraagde radius", "Punch radius {} exceeds the requested radius", "Oberwerkzeugradius {} ist größer als der geforderte Radius"]),
+    ("reason.force_over", ["Kracht {}kN overschrijdt perscapaciteit {}kN", "Force {}kN exceeds press capacity {}kN", "Kraft {}kN überschreitet Presskraft {}kN"]),
+    ("reason.force", ["Kracht ≈ {}kN", "Force ≈ {}kN", "Kraft ≈ {}kN"]),
+    ("reason.flange_short", ["Kortste flens {} < min. {}", "Shortest flange {} < min {}", "Kürzester Schenkel {} < min. {}"]),
+    ("reason.tool_angle_open", ["Gereedschapshoek (stempel {}°, matrijs {}°) te open voor {}°", "Tool angle (punch {}°, die {}°) too open for {}°", "Werkzeugwinkel (Oberwerkzeug {}°, Unterwerkzeug {}°) zu offen für {}°"]),
+    ("reason.no_overbend", ["Matrijshoek {}° laat geen ruimte om over te buigen", "Die angle {}° leaves no room for overbending", "Unterwerkzeugwinkel {}° lässt kein Überbiegen zu"]),
+    // Storage errors
+    ("storage.io", ["Bestandsfout: {}", "File I/O error: {}", "Dateifehler: {}"]),
+    ("storage.serialize", ["Fout bij opslaan: {}", "Serialization error: {}", "Fehler beim Speichern: {}"]),
+    ("storage.deserialize", ["Fout bij inlezen: {}", "Deserialization error: {}", "Fehler beim Einlesen: {}"]),
+    ("storage.not_found", ["Taak niet gevonden: {}", "Job not found: {}", "Auftrag nicht gefunden: {}"]),
+    ("storage.newer_version", ["Taakbestand heeft formaatversie {}, deze versie ondersteunt tot {}. Werk de simulator bij om het te openen.", "Job file has format version {}, this version supports up to {}. Update the simulator to open it.", "Auftragsdatei hat Formatversion {}, diese Version unterstützt bis {}. Simulator aktualisieren, um sie zu öffnen."]),
+    ("storage.unknown_format", ["Geen taakbestand: {}", "Not a job file: {}", "Keine Auftragsdatei: {}"]),
+];
+
+fn catalog() -> &'static HashMap<&'static str, [&'static str; 3]> {
+    static INDEX: OnceLock<HashMap<&'static str, [&'static str; 3]>> = OnceLock::new();
+    INDEX.get_or_init(|| CATALOG.iter().copied().collect())
+}
+
+/// Translation of `key`; unknown keys are shown as-is so they stand out.
+pub fn tr(lang: Language, key: &'static str) -> &'static str {
+    catalog().get(key).map_or(key, |texts| texts[lang.column()])
+}
+
+/// Translation of `key` with its `{}` placeholders replaced by `args` in order.
+pub fn trf(lang: Language, key: &'static str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut parts = tr(lang, key).split("{}");
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+// --- Library Types ---
+
+pub fn material_name(lang: Language, name: &MaterialName) -> String {
+    let key = match name {
+        MaterialName::Steel => "material.steel",
+        MaterialName::Aluminum => "material.aluminum",
+        MaterialName::StainlessSteel => "material.stainless_steel",
+        MaterialName::Copper => "material.copper",
+        MaterialName::MildSteel => "material.mild_steel",
+        MaterialName::Custom(name) => return name.clone(),
+    };
+    tr(lang, key).to_string()
+}
+
+pub fn direction_name(lang: Language, direction: &BendDirection) -> &'static str {
+    match direction {
+        BendDirection::Up => tr(lang, "direction.up"),
+        BendDirection::Down => tr(lang, "direction.down"),
+    }
+}
+
+pub fn support_name(lang: Language, support: PartSupport) -> &'static str {
+    match support {
+        PartSupport::Operator => tr(lang, "support.operator"),
+        PartSupport::SecondOperator => tr(lang, "support.second_operator"),
+        PartSupport::SheetFollower => tr(lang, "support.sheet_follower"),
+    }
+}
+
+pub fn rolling_direction_name(lang: Language, rolling: RollingDirection) -> &'static str {
+    match rolling {
+        RollingDirection::Unknown => tr(lang, "rolling.unknown"),
+        RollingDirection::AlongLength => tr(lang, "rolling.along_length"),
+        RollingDirection::AlongWidth => tr(lang, "rolling.along_width"),
+    }
+}
+
+pub fn method_name(lang: Language, method: BendMethod) -> &'static str {
+    match method {
+        BendMethod::AirBending => tr(lang, "method.air"),
+        BendMethod::Bottoming => tr(lang, "method.bottoming"),
+        BendMethod::Coining => tr(lang, "method.coining"),
+        BendMethod::Hemming => tr(lang, "method.hemming"),
+    }
+}
+
+pub fn feature_kind_name(lang: Language, kind: BendFeatureKind) -> &'static str {
+    match kind {
+        BendFeatureKind::Single => tr(lang, "feature.single"),
+        BendFeatureKind::HemOpen => tr(lang, "feature.hem_open"),
+        BendFeatureKind::HemClosed => tr(lang, "feature.hem_closed"),
+        BendFeatureKind::HemTeardrop => tr(lang, "feature.hem_teardrop"),
+        BendFeatureKind::Joggle => tr(lang, "feature.joggle"),
+        BendFeatureKind::Bump => tr(lang, "feature.bump"),
+    }
+}
+
+pub fn feature_name(lang: Language, unit: LengthUnit, feature: &BendFeature) -> String {
+    match feature {
+        BendFeature::Single => tr(lang, "feature.single").to_string(),
+        BendFeature::Hem(HemStyle::Open) => tr(lang, "feature.hem_open").to_string(),
+        BendFeature::Hem(HemStyle::Closed) => tr(lang, "feature.hem_closed").to_string(),
+        BendFeature::Hem(HemStyle::Teardrop) => tr(lang, "feature.hem_teardrop").to_string(),
+        BendFeature::Joggle { offset_mm } => trf(lang, "feature.joggle_offset", &[&unit.format(*offset_mm, 1)]),
+        BendFeature::Bump { hits, pitch_mm, .. } => trf(lang, "feature.bump_hits", &[hits, &unit.format(*pitch_mm, 1)]),
+    }
+}
+
+pub fn sheet_dimension_name(lang: Language, dimension: SheetDimension) -> &'static str {
+    match dimension {
+        SheetDimension::Length => tr(lang, "dimension.length"),
+        SheetDimension::Thickness => tr(lang, "dimension.thickness"),
+        SheetDimension::Width => tr(lang, "dimension.width"),
+    }
+}
+
+pub fn validation_error(lang: Language, unit: LengthUnit, error: &ValidationError) -> String {
+    let len = |mm: f64| unit.format(mm, 1);
+    match error {
+        ValidationError::SheetDimensionOutOfRange { dimension, .. } => trf(lang, "error.sheet_dimension", &[
+            &sheet_dimension_name(lang, *dimension), &len(validation::MIN_SHEET_DIMENSION_MM), &len(validation::MAX_SHEET_DIMENSION_MM),
+        ]),
+        ValidationError::PositionOutsideSheet { position_mm, length_mm } =>
+            trf(lang, "error.position_outside", &[&len(*position_mm), &len(*length_mm)]),
+        ValidationError::AngleOutOfRange { angle_deg } =>
+            trf(lang, "error.angle_range", &[angle_deg, &validation::MIN_BEND_ANGLE_DEG, &validation::MAX_BEND_ANGLE_DEG]),
+        ValidationError::RadiusOutOfRange { radius_mm } => trf(lang, "error.radius_range", &[
+            &len(*radius_mm), &len(validation::MIN_BEND_RADIUS_MM), &len(validation::MAX_BEND_RADIUS_MM),
+        ]),
+        ValidationError::InvalidJoggleOffset { offset_mm } => trf(lang, "error.joggle_offset", &[&len(*offset_mm)]),
+        ValidationError::HitOutsideSheet { position_mm, length_mm } =>
+            trf(lang, "error.hit_outside", &[&len(*position_mm), &len(*length_mm)]),
+        ValidationError::ToolsCannotAirBend { punch_angle_deg, die_angle_deg, target_angle_deg } =>
+            trf(lang, "error.cannot_air_bend", &[punch_angle_deg, die_angle_deg, target_angle_deg]),
+        ValidationError::ToolAngleMismatch { method, target_angle_deg, punch_angle_deg, die_angle_deg } =>
+            trf(lang, "error.tool_angle_mismatch", &[&method_name(lang, *method), target_angle_deg, punch_angle_deg, die_angle_deg]),
+        ValidationError::HemNotFlattened { .. } => trf(lang, "error.hem_not_flattened", &[&physics::HEM_FLATTEN_MAX_ANGLE_DEG]),
+        ValidationError::MissingHemPrebend { position_mm } =>
+            trf(lang, "error.missing_prebend", &[&len(*position_mm), &physics::HEM_PREBEND_MAX_ANGLE_DEG]),
+        ValidationError::ForceExceedsCapacity { method, force_kn } => trf(lang, "error.force_capacity", &[
+            &method_name(lang, *method), &format!("{:.0}", force_kn), &format!("{:.0}", physics::PRESS_CAPACITY_KN),
+        ]),
+        ValidationError::NoBendSteps => tr(lang, "error.no_steps").to_string(),
+        ValidationError::InStep { sequence_order, source } =>
+            trf(lang, "error.in_step", &[sequence_order, &validation_error(lang, unit, source)]),
+    }
+}
+
+pub fn validation_warning(lang: Language, unit: LengthUnit, warning: &ValidationWarning) -> String {
+    match warning {
+        ValidationWarning::RadiusBelowMaterialMinimum { radius_mm, min_radius_mm } =>
+            trf(lang, "warning.radius_below_min", &[&unit.format(*radius_mm, 2), &unit.format(*min_radius_mm, 2)]),
+        ValidationWarning::RadiusMismatch { sequence_order, method, punch, die, formed_radius_mm, requested_radius_mm } =>
+            trf(lang, "warning.radius_mismatch", &[
+                sequence_order, &method_name(lang, *method), punch, die,
+                &unit.format(*formed_radius_mm, 2), &unit.format(*requested_radius_mm, 2),
+            ]),
+        ValidationWarning::ShortFlanges(warnings) => {
+            let shortest = warnings.iter().map(|w| w.flange_mm).fold(f64::INFINITY, f64::min);
+            let min_flange = warnings.first().map_or(0.0, |w| w.min_flange_mm);
+            let orders: Vec<String> = warnings.iter().map(|w| w.sequence_order.to_string()).collect();
+            trf(lang, "warning.short_flanges", &[&unit.format(shortest, 1), &unit.format(min_flange, 1), &orders.join(", ")])
+        }
+        ValidationWarning::ConvertedToBump { radius_mm, feature } =>
+            trf(lang, "warning.converted_to_bump", &[&unit.format(*radius_mm, 1), &feature_name(lang, unit, feature)]),
+        ValidationWarning::BendAlongRollingDirection { sequence_order, radius_mm, min_radius_mm } =>
+            trf(lang, "warning.along_rolling", &[sequence_order, &unit.format(*radius_mm, 2), &unit.format(*min_radius_mm, 2)]),
+        ValidationWarning::HeavyPart { mass_kg, limit_kg } =>
+            trf(lang, "warning.heavy_part", &[&format!("{:.1}", mass_kg), &format!("{:.0}", limit_kg)]),
+        ValidationWarning::TipsAwayFromBackgauge { sequence_orders } => {
+            let orders: Vec<String> = sequence_orders.iter().map(|o| o.to_string()).collect();
+            trf(lang, "warning.tips_away", &[&orders.join(", ")])
+        }
+    }
+}
+
+/// V openings stay in their metric tool designation (V16), other lengths follow `unit`.
+pub fn recommendation_reason(lang: Language, unit: LengthUnit, reason: &RecommendationReason) -> String {
+    match reason {
+        RecommendationReason::VOpeningRatio { v_opening_mm, ratio, within_range: true } =>
+            trf(lang, "reason.v_ratio_ok", &[v_opening_mm, &format!("{:.1}", ratio), &physics::IDEAL_V_TO_THICKNESS]),
+        RecommendationReason::VOpeningRatio { v_opening_mm, ratio, within_range: false } =>
+            trf(lang, "reason.v_ratio_out", &[v_opening_mm, &format!("{:.1}", ratio)]),
+        RecommendationReason::EstimatedRadius { estimated_mm, requested_mm } =>
+            trf(lang, "reason.radius", &[&unit.format(*estimated_mm, 2), &unit.format(*requested_mm, 2)]),
+        RecommendationReason::PunchRadiusTooLarge { punch_radius_mm } => trf(lang, "reason.punch_radius", &[&unit.format(*punch_radius_mm, 1)]),
+        RecommendationReason::Force { force_kn, capacity_kn } if force_kn > capacity_kn =>
+            trf(lang, "reason.force_over", &[&format!("{:.0}", force_kn), &format!("{:.0}", capacity_kn)]),
+        RecommendationReason::Force { force_kn, .. } => trf(lang, "reason.force", &[&format!("{:.0}", force_kn)]),
+        RecommendationReason::FlangeTooShort { flange_mm, min_flange_mm } =>
+            trf(lang, "reason.flange_short", &[&unit.format(*flange_mm, 1), &unit.format(*min_flange_mm, 1)]),
+        RecommendationReason::ToolAngleTooOpen { punch_angle_deg, die_angle_deg, target_angle_deg } =>
+            trf(lang, "reason.tool_angle_open", &[punch_angle_deg, die_angle_deg, target_angle_deg]),
+        RecommendationReason::NoOverbendRoom { die_angle_deg } => trf(lang, "reason.no_overbend", &[die_angle_deg]),
+    }
+}
+
+pub fn numeric_input_error(lang: Language, unit: LengthUnit, input: &NumericInput, error: &NumericInputError) -> String {
+    match error {
+        NumericInputError::Empty => tr(lang, "input.empty").to_string(),
+        NumericInputError::InvalidExpression { position } => trf(lang, "input.invalid", &[&(position + 1)]),
+        NumericInputError::DivisionByZero => tr(lang, "input.division_by_zero").to_string(),
+        NumericInputError::OutOfRange { min, max } => {
+            let bound = |value: f64| match input.quantity {
+                Quantity::Length => unit.format(value, 1),
+                Quantity::Angle => format!("{}°", value),
+            };
+            trf(lang, "input.out_of_range", &[&bound(*min), &bound(*max)])
+        }
+    }
+}
+
+pub fn shift_name(lang: Language, shift: Shift) -> &'static str {
+    match shift {
+        Shift::Early => tr(lang, "shift.early"),
+        Shift::Late => tr(lang, "shift.late"),
+        Shift::Night => tr(lang, "shift.night"),
+    }
+}
+
+pub fn production_error(lang: Language, error: &ProductionError) -> String {
+    match error {
+        ProductionError::ZeroQuantity => tr(lang, "production.zero_quantity").to_string(),
+        ProductionError::UnknownOrder(id) => trf(lang, "production.unknown_order", &[id]),
+        ProductionError::ExceedsQuantity { order_id, remaining } => trf(lang, "production.exceeds_quantity", &[order_id, remaining]),
+    }
+}
+
+pub fn nest_error(lang: Language, error: &NestError) -> String {
+    match error {
+        NestError::NoBlanks => tr(lang, "nest.no_blanks").to_string(),
+        NestError::BlankTooLarge { job_name, plate } => trf(lang, "nest.too_large", &[job_name, plate]),
+    }
+}
+
+pub fn storage_error(lang: Language, error: &JobStorageError) -> String {
+    match error {
+        JobStorageError::IoError(e) => trf(lang, "storage.io", &[e]),
+        JobStorageError::SerializationError(e) => trf(lang, "storage.serialize", &[e]),
+        JobStorageError::DeserializationError(e) => trf(lang, "storage.deserialize", &[e]),
+        JobStorageError::NotFound(path) => trf(lang, "storage.not_found", &[path]),
+        JobStorageError::NewerVersion { found, supported } => trf(lang, "storage.newer_version", &[found, supported]),
+        JobStorageError::UnknownFormat(reason) => trf(lang, "storage.unknown_format", &[reason]),
+    }
+}
@@ -0,0 +1,18 @@
+// src/lib.rs
+//! Press brake job model, validation, geometry and physics, free of any GUI.
+//! The egui front end (`main.rs`) and scripts build on top of this crate.
+
+pub mod cycle_time;
+pub mod db;
+pub mod export;
+pub mod geometry;
+pub mod handling;
+pub mod model;
+pub mod nesting;
+pub mod physics;
+pub mod production;
+pub mod quote;
+pub mod simulation;
+pub mod tooling;
+pub mod units;
+pub mod validation;
@@ -1,23 +1,32 @@
 // src/logic.rs
-use crate::state::{AppState, BendStep, BendDirection, SheetMetal, MaterialName, Job, SheetInputState, BendInputState, ToolingInputState};
-use crate::db::{self, JobStorageError}; // Assuming db.rs is at this path
-use egui::{Context, Color32, Vec2, ColorImage, TextureHandle};
+// GUI glue: reads the input widgets, calls into the `vouwbank` library and
+// turns its typed results into status messages.
+use crate::assets::{self, Asset};
+use crate::i18n::{self, tr, trf};
+use crate::message_log::LogLevel;
+use crate::numeric_input::NumericInput;
+use crate::state::{self, AppState, BendInputState, JobLibraryEntry, JobTab, PendingJobAction, StorageErrorReport, ToolingInputState, UnsavedChangesChoice};
+use std::path::{Path, PathBuf};
+use vouwbank::cycle_time::{self, CycleTime};
+use vouwbank::db;
+use vouwbank::export;
+use vouwbank::geometry::{self, FlangeWarning};
+use vouwbank::handling::{self, FlangeLift, PartHandling};
+use vouwbank::model::{BendDirection, BendFeature, BendFeatureKind, BendMethod, BendStep, HemStyle, Job, MachineSetup, MaterialName, RollingDirection, SheetMetal};
+use vouwbank::nesting::{self, Blank, Nest, NestError};
+use vouwbank::quote::{self, Quote};
+use vouwbank::simulation;
+use vouwbank::tooling;
+use vouwbank::units::{self, GaugeStandard, LengthUnit};
+use vouwbank::validation::{self, SheetDimension, ValidationError, ValidationWarning};
+use egui::{Context, Vec2, ColorImage, TextureHandle, ViewportCommand};
 use image::GenericImageView;
-use std::path::Path;
-
-// --- Config Constants (could be in a separate config.rs) ---
-const MIN_SHEET_DIMENSION_MM: f64 = 0.1;
-const MAX_SHEET_DIMENSION_MM: f64 = 10000.0;
-const MIN_BEND_RADIUS_MM: f64 = 0.0; // 0 can mean sharp
-const MAX_BEND_RADIUS_MM: f64 = 500.0;
-const MIN_BEND_ANGLE_DEG: f64 = 1.0;
-const MAX_BEND_ANGLE_DEG: f64 = 179.0;
 
 
 // --- Image Logic (from previous skeleton, adapted) ---
 #[derive(Debug, thiserror::Error)]
 pub enum ImageLogicError {
-    #[error("Failed to load image from path: {0}")]
+    #[error("Failed to read image: {0}")]
     IoError(#[from] std::io::Error),
     #[error("Failed to decode image: {0}")]
     ImageError(#[from] image::ImageError),
@@ -25,11 +34,10 @@ pub enum ImageLogicError {
 
 fn load_image_and_create_texture(
     ctx: &Context,
-    image_path_str: &str,
+    image_data: &[u8],
     texture_name: &str,
 ) -> Result<(TextureHandle, Vec2), ImageLogicError> {
-    let image_data = std::fs::read(image_path_str)?;
-    let image_dyn = image::load_from_memory(&image_data)?;
+    let image_dyn = image::load_from_memory(image_data)?;
     let (width, height) = image_dyn.dimensions();
     let image_rgba = image_dyn.to_rgba8();
 
@@ -49,245 +57,958 @@ fn load_image_and_create_texture(
 
 // --- CNC Specific Logic ---
 
-pub fn update_sheet_properties(state: &mut AppState) {
-    let parse_f64 = |s: &str, field_name: &str| -> Result<f64, String> {
-        s.parse::<f64>().map_err(|_| format!("Invalid {}: '{}' is not a valid number.", field_name, s))
-    };
+// --- Input Fields ---
 
-    let length = match parse_f64(&state.sheet_input.length_mm_str, "Length") {
-        Ok(l) if l >= MIN_SHEET_DIMENSION_MM && l <= MAX_SHEET_DIMENSION_MM => l,
-        Ok(_) => {
-            state.status_message = (format!("Length out of range ({}-{}mm).", MIN_SHEET_DIMENSION_MM, MAX_SHEET_DIMENSION_MM), Some(Color32::RED));
-            return;
-        }
-        Err(e) => { state.status_message = (e, Some(Color32::RED)); return; }
-    };
-    // Similar parsing and validation for thickness and width
-    let thickness = match parse_f64(&state.sheet_input.thickness_mm_str, "Thickness") {
-        Ok(t) if t >= MIN_SHEET_DIMENSION_MM && t <= MAX_SHEET_DIMENSION_MM => t,
-        Ok(_) => {
-            state.status_message = (format!("Thickness out of range ({}-{}mm).", MIN_SHEET_DIMENSION_MM, MAX_SHEET_DIMENSION_MM), Some(Color32::RED));
-            return;
-        }
-        Err(e) => { state.status_message = (e, Some(Color32::RED)); return; }
-    };
-     let width = match parse_f64(&state.sheet_input.width_mm_str, "Width") {
-        Ok(w) if w >= MIN_SHEET_DIMENSION_MM && w <= MAX_SHEET_DIMENSION_MM => w,
-        Ok(_) => {
-            state.status_message = (format!("Width out of range ({}-{}mm).", MIN_SHEET_DIMENSION_MM, MAX_SHEET_DIMENSION_MM), Some(Color32::RED));
-            return;
-        }
-        Err(e) => { state.status_message = (e, Some(Color32::RED)); return; }
-    };
+/// The field's value in mm or degrees, or why it has none in the current language.
+pub fn input_value(state: &AppState, input: &NumericInput) -> Result<f64, String> {
+    input.value(state.length_unit).map_err(|e| i18n::numeric_input_error(state.language, state.length_unit, input, &e))
+}
 
+/// `input_value` prefixed with the field name, for the status bar.
+fn named_input_value(state: &AppState, field_name: &str, input: &NumericInput) -> Result<f64, String> {
+    input_value(state, input).map_err(|e| trf(state.language, "input.field_error", &[&field_name, &e]))
+}
 
-    let selected_material_name = state.material_display_order.get(state.sheet_input.selected_material_idx)
+fn selected_material_name(state: &AppState) -> MaterialName {
+    state.material_display_order.get(state.sheet_input.selected_material_idx)
         .cloned()
         .unwrap_or_else(|| {
             // Fallback if index is somehow out of bounds, though UI should prevent this.
             // Or, if material_display_order is empty (which it shouldn't be from Default).
             state.material_display_order.first().cloned().unwrap_or(MaterialName::Steel)
-        });
+        })
+}
 
+/// Thickness also accepts a US gauge number ("16ga"), looked up in the selected material's gauge table.
+pub fn thickness_input_value(state: &AppState, input: &NumericInput) -> Result<f64, String> {
+    let Some(gauge) = units::parse_gauge(&input.text) else {
+        return input_value(state, input);
+    };
+    let standard = GaugeStandard::for_material(&selected_material_name(state));
+    standard.thickness_mm(gauge).ok_or_else(|| trf(state.language, "sheet.unknown_gauge", &[&gauge, &standard]))
+}
 
-    state.current_job.sheet.original_length_mm = length;
-    state.current_job.sheet.thickness_mm = thickness;
-    state.current_job.sheet.width_mm = width;
-    state.current_job.sheet.material_name = selected_material_name;
-    state.current_job.steps.clear(); // Changing sheet properties invalidates old bends
+/// Bend positions must fall on the current sheet.
+fn sync_input_ranges(state: &mut AppState) {
+    state.bend_input.position.max = state.tab().job.sheet.original_length_mm;
+}
+
+// --- CNC Specific Logic ---
+
+pub fn update_sheet_properties(state: &mut AppState) {
+    let (lang, unit) = (state.language, state.length_unit);
+    let selected_material_name = selected_material_name(state);
+    let parsed = named_input_value(state, i18n::sheet_dimension_name(lang, SheetDimension::Length), &state.sheet_input.length).and_then(|length| {
+        let thickness = thickness_input_value(state, &state.sheet_input.thickness)
+            .map_err(|e| trf(lang, "input.field_error", &[&i18n::sheet_dimension_name(lang, SheetDimension::Thickness), &e]))?;
+        let width = named_input_value(state, i18n::sheet_dimension_name(lang, SheetDimension::Width), &state.sheet_input.width)?;
+        Ok((length, thickness, width))
+    });
+    let (length, thickness, width) = match parsed {
+        Ok(values) => values,
+        Err(e) => { state.log(LogLevel::Error, e); return; }
+    };
+
+    let sheet = SheetMetal {
+        original_length_mm: length,
+        thickness_mm: thickness,
+        width_mm: width,
+        material_name: selected_material_name,
+        ..state.tab().job.sheet.clone()
+    };
+    if let Err(e) = validation::validate_sheet_dimensions(&sheet) {
+        state.log(LogLevel::Error, i18n::validation_error(lang, unit, &e));
+        return;
+    }
 
-    state.status_message = ("Sheet properties updated. Bend steps cleared.".to_string(), Some(Color32::GREEN));
-    state.simulated_profile_texture = None; // Clear old profile
-    state.profile_load_status = "Profile outdated due to sheet change.".to_string();
+    record_undo(state);
+    let job = &mut state.tab_mut().job;
+    job.sheet = sheet;
+    job.steps.clear(); // Changing sheet properties invalidates old bends
+    mark_job_changed(state, "profile.outdated_sheet");
+    sync_input_ranges(state);
+    state.log(LogLevel::Success, tr(lang, "sheet.updated"));
 }
 
 pub fn get_recommended_min_bend_radius(state: &AppState) -> Option<f64> {
-    let sheet = &state.current_job.sheet;
-    state.available_materials.get(&sheet.material_name).map(|details| {
-        if sheet.thickness_mm <= 0.0 { return 0.0; }
-        if details.min_bend_radius_factor <= 0.0 {
-            sheet.thickness_mm * 0.5 // Default fallback
-        } else {
-            sheet.thickness_mm * details.min_bend_radius_factor
-        }
+    let sheet = &state.tab().job.sheet;
+    state.available_materials.get(&sheet.material_name)
+        .map(|details| details.min_bend_radius_mm(sheet.thickness_mm, sheet.rolling_direction))
+}
+
+/// Changes the rolling direction of the current sheet. Unlike the dimensions
+/// this keeps the bend steps; steps it makes too tight are logged.
+pub fn set_rolling_direction(state: &mut AppState, rolling: RollingDirection) {
+    let (lang, unit) = (state.language, state.length_unit);
+    if state.tab().job.sheet.rolling_direction == rolling {
+        return;
+    }
+    record_undo(state);
+    state.tab_mut().job.sheet.rolling_direction = rolling;
+    mark_job_changed(state, "profile.outdated_sheet");
+    state.log(LogLevel::Info, trf(lang, "sheet.rolling_changed", &[&i18n::rolling_direction_name(lang, rolling)]));
+    let Some(material) = state.available_materials.get(&state.tab().job.sheet.material_name) else { return };
+    let warnings = validation::rolling_direction_warnings(material, &state.tab().job);
+    for warning in warnings {
+        state.log(LogLevel::Warning, i18n::validation_warning(lang, unit, &warning));
+    }
+}
+
+/// The current sheet with the punch and die selected in the tooling setup.
+pub fn machine_setup(state: &AppState) -> Option<MachineSetup<'_>> {
+    Some(MachineSetup {
+        sheet: &state.tab().job.sheet,
+        material: state.available_materials.get(&state.tab().job.sheet.material_name)?,
+        punch: state.available_punches.get(state.tooling_input.selected_punch_idx)?,
+        die: state.available_dies.get(state.tooling_input.selected_die_idx)?,
     })
 }
 
+/// Cycle time of one part of the active job with the selected tools.
+pub fn cycle_time_estimate(state: &AppState) -> Option<CycleTime> {
+    let setup = machine_setup(state)?;
+    Some(cycle_time::estimate(&setup, &state.tab().job, &state.machine_speeds))
+}
 
-pub fn add_bend_step(state: &mut AppState) {
-    let parse_f64 = |s: &str, field_name: &str| -> Result<f64, String> {
-        s.parse::<f64>().map_err(|_| format!("Ongeldige {}: '{}'", field_name, s))
-    };
+pub fn part_handling(state: &AppState) -> Option<PartHandling> {
+    let setup = machine_setup(state)?;
+    Some(handling::analyse(&setup, &state.tab().job))
+}
 
-    let position = match parse_f64(&state.bend_input.position_mm_str, "Buig Positie") {
-        Ok(p) if p > 0.0 && p < state.current_job.sheet.original_length_mm => p,
-        Ok(p) => {
-            state.status_message = (format!("Buig posititie ({}mm) is buiten de plaat lengte (0-{}mm).", p, state.current_job.sheet.original_length_mm), Some(Color32::RED));
-            return;
+/// Front flange lift per step, empty without a complete tooling setup.
+pub fn flange_lifts(state: &AppState) -> Vec<FlangeLift> {
+    match machine_setup(state) {
+        Some(setup) => handling::flange_lifts(&setup, &state.tab().job, &state.machine_speeds),
+        None => Vec::new(),
+    }
+}
+
+/// Flange warnings for the die currently selected in the tooling setup.
+pub fn selected_die_flange_warnings(state: &AppState) -> Vec<FlangeWarning> {
+    match state.available_dies.get(state.tooling_input.selected_die_idx) {
+        Some(die) => geometry::check_min_flange_lengths(&state.tab().job, die),
+        None => Vec::new(),
+    }
+}
+
+/// Logs each validation warning on its own; short flanges get a die suggestion
+/// from the tooling library.
+fn log_warnings(state: &mut AppState, warnings: &[ValidationWarning]) {
+    let lang = state.language;
+    for warning in warnings {
+        let mut text = i18n::validation_warning(lang, state.length_unit, warning);
+        if let ValidationWarning::ShortFlanges(flanges) = warning {
+            let shortest = flanges.iter().map(|w| w.flange_mm).fold(f64::INFINITY, f64::min);
+            text.push(' ');
+            match tooling::suggest_die_for_flange(&state.available_dies, shortest) {
+                Some(die) => text.push_str(&trf(lang, "tooling.flange_try_die", &[&die.name, &die.v_opening_mm])),
+                None => text.push_str(tr(lang, "tooling.flange_no_die")),
+            }
         }
-        Err(e) => { state.status_message = (e, Some(Color32::RED)); return; }
+        state.log(LogLevel::Warning, text);
+    }
+}
+
+// --- Tooling Recommendation ---
+
+pub fn update_tooling_recommendations(state: &mut AppState) {
+    let lang = state.language;
+    let radius = input_value(state, &state.bend_input.radius);
+    let angle = input_value(state, &state.bend_input.target_angle);
+    let (Ok(radius), Ok(angle)) = (radius, angle) else {
+        state.log(LogLevel::Error, tr(lang, "tooling.need_radius_angle"));
+        return;
     };
-    // Similar parsing and validation for angle and radius
-    let angle = match parse_f64(&state.bend_input.target_angle_deg_str, "Buig Hoek") {
-        Ok(a) if a >= MIN_BEND_ANGLE_DEG && a <= MAX_BEND_ANGLE_DEG => a,
-        Ok(a) => {
-            state.status_message = (format!("Buig hoek ({}°) buiten bereik van ({}-{}°).", a, MIN_BEND_ANGLE_DEG, MAX_BEND_ANGLE_DEG), Some(Color32::RED));
-            return;
-        }
-        Err(e) => { state.status_message = (e, Some(Color32::RED)); return; }
+    let Some(material) = state.available_materials.get(&state.tab().job.sheet.material_name) else {
+        return;
     };
-    let radius = match parse_f64(&state.bend_input.radius_mm_str, "Buig Radius") {
-        Ok(r) if r >= MIN_BEND_RADIUS_MM && r <= MAX_BEND_RADIUS_MM => r,
-        Ok(r) => {
-             state.status_message = (format!("Buig radius ({}mm) buiten bereik van ({}-{}mm).", r, MIN_BEND_RADIUS_MM, MAX_BEND_RADIUS_MM), Some(Color32::RED));
-            return;
+    state.tooling_recommendations = tooling::recommend_tooling(
+        &state.tab().job, material, &state.available_punches, &state.available_dies, radius, angle,
+    );
+    match state.tooling_recommendations.first() {
+        Some(best) if best.usable => {
+            state.log(LogLevel::Success, trf(lang, "tooling.recommended", &[
+                &state.available_punches[best.punch_idx].name, &state.available_dies[best.die_idx].name,
+            ]));
         }
-        Err(e) => { state.status_message = (e, Some(Color32::RED)); return; }
+        _ => state.log(LogLevel::Warning, tr(lang, "tooling.none_suitable")),
+    }
+}
+
+pub fn apply_tooling_recommendation(state: &mut AppState, recommendation_idx: usize) {
+    if let Some(rec) = state.tooling_recommendations.get(recommendation_idx) {
+        state.tooling_input.selected_punch_idx = rec.punch_idx;
+        state.tooling_input.selected_die_idx = rec.die_idx;
+        state.log(LogLevel::Success, tr(state.language, "tooling.updated"));
+    }
+}
+
+// --- Bend Steps ---
+
+pub fn selected_feature_kind(state: &AppState) -> BendFeatureKind {
+    BendFeatureKind::all()
+        .get(state.bend_input.selected_feature_idx)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// The feature picked for a new step; bumps are planned for the step's radius
+/// with the selected tools.
+fn feature_from_input(state: &AppState, setup: &MachineSetup, step: &BendStep) -> Result<BendFeature, String> {
+    match selected_feature_kind(state) {
+        BendFeatureKind::HemOpen => Ok(BendFeature::Hem(HemStyle::Open)),
+        BendFeatureKind::HemClosed => Ok(BendFeature::Hem(HemStyle::Closed)),
+        BendFeatureKind::HemTeardrop => Ok(BendFeature::Hem(HemStyle::Teardrop)),
+        BendFeatureKind::Joggle => named_input_value(state, tr(state.language, "bend.field_joggle_offset"), &state.bend_input.joggle_offset)
+            .map(|offset_mm| BendFeature::Joggle { offset_mm }),
+        BendFeatureKind::Bump => Ok(validation::bump_plan(setup, step)),
+        BendFeatureKind::Single => Ok(BendFeature::Single),
+    }
+}
+
+pub fn add_bend_step(state: &mut AppState) {
+    let (lang, unit) = (state.language, state.length_unit);
+    let input = &state.bend_input;
+    let parsed = named_input_value(state, tr(lang, "bend.field_position"), &input.position).and_then(|position| {
+        let angle = named_input_value(state, tr(lang, "bend.field_angle"), &input.target_angle)?;
+        let radius = named_input_value(state, tr(lang, "bend.field_radius"), &input.radius)?;
+        Ok((position, angle, radius))
+    });
+    let (position, angle, radius) = match parsed {
+        Ok(values) => values,
+        Err(e) => { state.log(LogLevel::Error, e); return; }
     };
 
     let direction = BendDirection::default_directions()
         .get(state.bend_input.selected_direction_idx)
         .cloned()
         .unwrap_or(BendDirection::Up); // Fallback
+    let method = BendMethod::default_methods()
+        .get(state.bend_input.selected_method_idx)
+        .copied()
+        .unwrap_or(BendMethod::AirBending);
 
-    // Optional: Check against recommended min bend radius
-    if let Some(min_recommended_radius) = get_recommended_min_bend_radius(state) {
-        if radius > 1e-6 && radius < min_recommended_radius { // allow 0 for sharp/coining effectively
-            // For now, just a log or status. A real app might use a dialog as in the Gio example.
-            state.status_message = (format!("Warning: Radius {:.2}mm < recommended min {:.2}mm for material.", radius, min_recommended_radius), Some(Color32::YELLOW));
-        }
-    }
-
-
-    let new_step = BendStep {
-        sequence_order: state.current_job.steps.len() + 1,
+    let mut new_step = BendStep {
+        sequence_order: state.tab().job.steps.len() + 1,
         position_mm: position,
         target_angle_deg: angle,
         radius_mm: radius,
         direction,
+        method,
+        feature: BendFeature::Single,
     };
-    state.current_job.steps.push(new_step);
-    state.status_message = ("Buig stap toegevoegd.".to_string(), Some(Color32::GREEN));
-    state.simulated_profile_texture = None; // Profile outdated
-    state.profile_load_status = "Profile outdated due to new bend.".to_string();
+    let Some(setup) = machine_setup(state) else {
+        state.log(LogLevel::Error, tr(lang, "tooling.none_selected"));
+        return;
+    };
+    new_step.feature = match feature_from_input(state, &setup, &new_step) {
+        Ok(feature) => feature,
+        Err(e) => { state.log(LogLevel::Error, e); return; }
+    };
+    let bump_requested = matches!(new_step.feature, BendFeature::Bump { .. });
+    let prepared = match validation::prepare_step(&setup, &state.tab().job, new_step, bump_requested) {
+        Ok(prepared) => prepared,
+        Err(e) => { state.log(LogLevel::Error, i18n::validation_error(lang, unit, &e)); return; }
+    };
+
+    state.log(LogLevel::Success, trf(lang, "bend.added", &[&prepared.step.sequence_order]));
+    log_warnings(state, &prepared.warnings);
+    record_undo(state);
+    state.tab_mut().job.steps.push(prepared.step);
+    mark_job_changed(state, "profile.outdated_bend");
 }
 
 pub fn clear_all_bend_steps(state: &mut AppState) {
-    if state.current_job.steps.is_empty() {
-        state.status_message = ("No bend steps to clear.".to_string(), None);
+    let lang = state.language;
+    if state.tab().job.steps.is_empty() {
+        state.log(LogLevel::Info, tr(lang, "sequence.nothing_to_clear"));
         return;
     }
-    state.current_job.steps.clear();
-    state.status_message = ("All bend steps cleared.".to_string(), Some(Color32::GREEN));
-    state.simulated_profile_texture = None; // Profile outdated
-    state.profile_load_status = "Profile outdated, bends cleared.".to_string();
+    record_undo(state);
+    state.tab_mut().job.steps.clear();
+    mark_job_changed(state, "profile.outdated_cleared");
+    state.log(LogLevel::Success, tr(lang, "sequence.cleared"));
+}
+
+// --- Edit History ---
+
+/// Remembers the active job as it is before an edit.
+fn record_undo(state: &mut AppState) {
+    let tab = state.tab_mut();
+    tab.undo_stack.push(tab.job.clone());
+    if tab.undo_stack.len() > state::MAX_UNDO_STEPS {
+        tab.undo_stack.remove(0);
+    }
+    tab.redo_stack.clear();
 }
 
-pub fn run_simulation(state: &mut AppState, ctx: &Context) {
-    if state.current_job.steps.is_empty() {
-        state.status_message = ("No bend steps to simulate.".to_string(), Some(Color32::YELLOW));
+/// After every edit of the active job: unsaved, and the old profile no longer applies.
+fn mark_job_changed(state: &mut AppState, profile_status: &'static str) {
+    let tab = state.tab_mut();
+    tab.dirty = true;
+    tab.selected_steps.clear();
+    tab.simulation_result = None;
+    tab.profile_load_status = profile_status;
+}
+
+pub fn undo(state: &mut AppState) {
+    let tab = state.tab_mut();
+    let Some(previous) = tab.undo_stack.pop() else { return };
+    let current = std::mem::replace(&mut tab.job, previous);
+    tab.redo_stack.push(current);
+    mark_job_changed(state, "profile.outdated_undo");
+    sync_sheet_input_from_job(state);
+    state.log(LogLevel::Info, tr(state.language, "edit.undone"));
+}
+
+pub fn redo(state: &mut AppState) {
+    let tab = state.tab_mut();
+    let Some(next) = tab.redo_stack.pop() else { return };
+    let current = std::mem::replace(&mut tab.job, next);
+    tab.undo_stack.push(current);
+    mark_job_changed(state, "profile.outdated_undo");
+    sync_sheet_input_from_job(state);
+    state.log(LogLevel::Info, tr(state.language, "edit.redone"));
+}
+
+/// Copies the selected bend steps of the active job, or all of them when none are selected.
+pub fn copy_bend_steps(state: &mut AppState) {
+    let tab = state.tab();
+    let steps: Vec<BendStep> = tab.job.steps.iter().enumerate()
+        .filter(|(i, _)| tab.selected_steps.is_empty() || tab.selected_steps.contains(i))
+        .map(|(_, step)| step.clone())
+        .collect();
+    if steps.is_empty() {
+        state.log(LogLevel::Info, tr(state.language, "edit.nothing_to_copy"));
         return;
     }
-    // --- Actual simulation logic would go here ---
-    // This might involve complex geometry calculations to determine the final shape.
-    // For this skeleton, we'll just log it and generate a placeholder "profile".
-    state.simulation_status = format!("Simulating {} bend steps for job '{}'...", state.current_job.steps.len(), state.current_job.name);
-    state.status_message = (state.simulation_status.clone(), None);
-    println!("{}", state.simulation_status);
-    for step in &state.current_job.steps {
-        println!("  Simulating Step {}: Pos: {}, Angle: {}, Rad: {}, Dir: {:?}",
-            step.sequence_order, step.position_mm, step.target_angle_deg, step.radius_mm, step.direction);
-    }
-
-    // Simulate generating an SVG and loading it as a texture
-    // In a real app, you'd call a proper SVG generation function.
-    // For now, let's try to load the app_logo as a placeholder "profile".
-    // This section should ideally generate an SVG based on `state.current_job`
-    // then render that SVG to a `ColorImage` (e.g. using `resvg`), then load as texture.
-    // That's quite involved, so we'll reuse the app logo loading logic for now.
-
-    let temp_profile_path = "assets/drawing.png"; // Placeholder
-    state.profile_load_status = format!("Generating profile (using placeholder: {})...", temp_profile_path);
-    ctx.request_repaint(); // show status update
-
-    match load_image_and_create_texture(ctx, temp_profile_path, "simulated_profile") {
-        Ok((texture, size)) => {
-            state.simulated_profile_texture = Some(texture);
-            state.simulated_profile_size = Some(size);
-            state.profile_load_status = "Simulated profile loaded (placeholder).".to_string();
+    let message = trf(state.language, "edit.copied", &[&steps.len(), &tab.job.name]);
+    state.step_clipboard = steps;
+    state.log(LogLevel::Success, message);
+}
+
+/// Appends the copied steps to the active job. Each step is checked against this
+/// job's sheet and tooling like a newly added one; steps that don't fit are skipped.
+pub fn paste_bend_steps(state: &mut AppState) {
+    let (lang, unit) = (state.language, state.length_unit);
+    if state.step_clipboard.is_empty() {
+        state.log(LogLevel::Info, tr(lang, "edit.clipboard_empty"));
+        return;
+    }
+    let Some(setup) = machine_setup(state) else {
+        state.log(LogLevel::Error, tr(lang, "tooling.none_selected"));
+        return;
+    };
+    let mut job = state.tab().job.clone();
+    let mut warnings = Vec::new();
+    let mut rejected = Vec::new();
+    for copied in &state.step_clipboard {
+        let step = BendStep { sequence_order: job.steps.len() + 1, ..copied.clone() };
+        let bump_requested = matches!(step.feature, BendFeature::Bump { .. }); // Re-plan for this sheet
+        match validation::prepare_step(&setup, &job, step, bump_requested) {
+            Ok(prepared) => {
+                warnings.extend(prepared.warnings);
+                job.steps.push(prepared.step);
+            }
+            Err(e) => rejected.push(trf(lang, "edit.paste_skipped", &[&copied.sequence_order, &i18n::validation_error(lang, unit, &e)])),
         }
-        Err(e) => {
-            state.profile_load_status = format!("Fout laden profiel afbeelding: {}", e);
-            state.simulated_profile_texture = None;
+    }
+    let pasted = job.steps.len() - state.tab().job.steps.len();
+    for message in rejected {
+        state.log(LogLevel::Warning, message);
+    }
+    if pasted == 0 {
+        return;
+    }
+    record_undo(state);
+    state.tab_mut().job = job;
+    mark_job_changed(state, "profile.outdated_bend");
+    state.log(LogLevel::Success, trf(lang, "edit.pasted", &[&pasted]));
+    log_warnings(state, &warnings);
+}
+
+pub fn run_simulation(state: &mut AppState) {
+    let (lang, unit) = (state.language, state.length_unit);
+    let Some(setup) = machine_setup(state) else {
+        state.log(LogLevel::Error, tr(lang, "tooling.none_selected"));
+        return;
+    };
+    let result = match simulation::simulate(&setup, &state.tab().job) {
+        Ok(result) => result,
+        Err(ValidationError::NoBendSteps) => {
+            state.log(LogLevel::Warning, i18n::validation_error(lang, unit, &ValidationError::NoBendSteps));
+            return;
         }
+        Err(e) => { state.log(LogLevel::Error, i18n::validation_error(lang, unit, &e)); return; }
+    };
+
+    state.log(LogLevel::Debug, trf(lang, "exec.simulating", &[&state.tab().job.steps.len(), &result.hits.len(), &state.tab().job.name]));
+    for step in &result.hits {
+        state.log(LogLevel::Debug, trf(lang, "exec.simulating_hit", &[
+            &step.sequence_order, &unit.format(step.position_mm, 2), &step.target_angle_deg, &unit.format(step.radius_mm, 2),
+            &i18n::direction_name(lang, &step.direction), &i18n::method_name(lang, step.method),
+        ]));
     }
 
-    state.parts_bent_session += 1;
-    state.simulation_status = "Simulatie compleet.".to_string();
-    state.status_message = ("Simulatie compleet.".to_string(), Some(Color32::GREEN));
+    state.tab_mut().profile_load_status = "profile.generated";
+    state.tab_mut().simulation_status = "exec.complete";
+    state.log(LogLevel::Success, tr(lang, "exec.complete"));
+    log_warnings(state, &result.warnings);
+    state.tab_mut().simulation_result = Some(result);
 }
 
 
+/// Texture for an asset; a broken override file falls back to the embedded copy.
+fn load_asset_texture(ctx: &Context, state: &mut AppState, asset: Asset) -> Result<(TextureHandle, Vec2), ImageLogicError> {
+    let texture_name = asset.file_name();
+    if let Some(path) = assets::override_path(asset) {
+        let overridden = assets::load(asset)
+            .map_err(ImageLogicError::from)
+            .and_then(|data| load_image_and_create_texture(ctx, &data, texture_name));
+        match overridden {
+            Ok(texture) => {
+                state.log(LogLevel::Debug, trf(state.language, "assets.override_used", &[&path.display()]));
+                return Ok(texture);
+            }
+            Err(e) => state.log(LogLevel::Warning, trf(state.language, "assets.override_failed", &[&path.display(), &e])),
+        }
+    }
+    load_image_and_create_texture(ctx, asset.embedded(), texture_name)
+}
+
 pub fn perform_initial_setup(ctx: &Context, state: &mut AppState) {
-    // Load app logo (example from previous skeleton)
-    match load_image_and_create_texture(ctx, "assets/drawing.png", "app_logo") {
+    match load_asset_texture(ctx, state, Asset::Logo) {
         Ok((texture, size)) => {
             state.app_logo_texture = Some(texture);
             state.app_logo_size = Some(size);
         }
         Err(e) => {
-            eprintln!("Failed to load app logo: {}", e);
-            // Update status message if you have one for app-level errors
-            state.status_message = (format!("Failed to load app logo: {}", e), Some(Color32::RED));
+            state.log(LogLevel::Error, trf(state.language, "file.logo_failed", &[&e]));
         }
     }
 
-    // You could load a default job here, or initialize other CNC specific things.
-    state.sheet_input.length_mm_str = state.current_job.sheet.original_length_mm.to_string();
-    state.sheet_input.thickness_mm_str = state.current_job.sheet.thickness_mm.to_string();
-    state.sheet_input.width_mm_str = state.current_job.sheet.width_mm.to_string();
-    state.sheet_input.selected_material_idx = state.material_display_order.iter().position(|n| *n == state.current_job.sheet.material_name).unwrap_or(0);
+    // The app restores the previous session on top of these defaults
+    sync_sheet_input_from_job(state);
+    set_default_bend_inputs(state);
+}
 
+/// Convenient starting values until the operator's own inputs are restored.
+fn set_default_bend_inputs(state: &mut AppState) {
+    let unit = state.length_unit;
+    state.bend_input = BendInputState::default();
+    state.bend_input.position.set_value(50.0, unit);
+    state.bend_input.target_angle.set_value(90.0, unit);
+    state.bend_input.radius.set_value(2.0, unit);
+    state.bend_input.joggle_offset.set_value(5.0, unit);
+    sync_input_ranges(state);
+}
 
-    // Populate default bend input values for convenience
-    state.bend_input.position_mm_str = "50.0".to_string();
-    state.bend_input.target_angle_deg_str = "90.0".to_string();
-    state.bend_input.radius_mm_str = "2.0".to_string();
+fn sync_sheet_input_from_job(state: &mut AppState) {
+    let unit = state.length_unit;
+    let sheet = &state.tabs[state.active_tab].job.sheet; // Field borrow, so sheet_input stays writable
+    state.sheet_input.length.set_value(sheet.original_length_mm, unit);
+    state.sheet_input.thickness.set_value(sheet.thickness_mm, unit);
+    state.sheet_input.width.set_value(sheet.width_mm, unit);
+    sync_input_ranges(state);
+    state.sheet_input.selected_material_idx = state.material_display_order.iter().position(|n| *n == state.tab().job.sheet.material_name).unwrap_or(0);
 }
 
-pub fn handle_save_job(state: &AppState, file_path: Option<String>) {
-    if let Some(path) = file_path {
-        match db::save_job_to_file(&state.current_job, &path) {
-            Ok(_) => println!("Job '{}' saved to '{}'", state.current_job.name, path), // Update state.status_message
-            Err(e) => eprintln!("Failed to save job: {}", e), // Update state.status_message
+/// Switches the display unit and rewrites the length input fields in it. Fields
+/// that don't evaluate (empty, gauge entry, typos) are left alone.
+pub fn set_length_unit(state: &mut AppState, unit: LengthUnit) {
+    let previous = state.length_unit;
+    if unit == previous {
+        return;
+    }
+    let sheet_input = &mut state.sheet_input;
+    let bend_input = &mut state.bend_input;
+    for field in [
+        &mut sheet_input.length, &mut sheet_input.thickness, &mut sheet_input.width,
+        &mut bend_input.position, &mut bend_input.radius, &mut bend_input.joggle_offset,
+    ] {
+        if let Ok(value) = field.evaluate(previous) {
+            field.set_value(value, unit);
         }
+    }
+    state.length_unit = unit;
+}
+
+// --- Session ---
+
+/// Bend inputs from the last session, in the restored length unit.
+pub fn restore_bend_input(state: &mut AppState, bend_input: BendInputState) {
+    state.bend_input = bend_input;
+    sync_input_ranges(state);
+}
+
+/// Reopens a job that was open when the app was last closed.
+pub fn restore_job(state: &mut AppState, job: Job, file: Option<PathBuf>, dirty: bool) {
+    let message = trf(state.language, "session.restored", &[&job.name]);
+    open_tab(state, JobTab { dirty, ..JobTab::new(job, file) });
+    state.log(LogLevel::Info, message);
+}
+
+/// Selects tools by name; names no longer in the tooling library keep the current selection.
+pub fn select_tools_by_name(state: &mut AppState, punch_name: &str, die_name: &str) {
+    if let Some(idx) = state.available_punches.iter().position(|p| p.name == punch_name) {
+        state.tooling_input.selected_punch_idx = idx;
+    }
+    if let Some(idx) = state.available_dies.iter().position(|d| d.name == die_name) {
+        state.tooling_input.selected_die_idx = idx;
+    }
+}
+
+/// Back to the out-of-the-box language, unit, tools, inputs, panel widths and
+/// window size. Open jobs and the recent files are kept.
+pub fn reset_settings(ctx: &Context, state: &mut AppState) {
+    state.language = i18n::Language::default();
+    state.length_unit = LengthUnit::default();
+    state.tooling_input = ToolingInputState::default();
+    state.tooling_recommendations.clear();
+    sync_sheet_input_from_job(state);
+    set_default_bend_inputs(state);
+    ctx.memory_mut(|memory| *memory = Default::default()); // Panel widths and other egui layout state
+    ctx.send_viewport_cmd(ViewportCommand::InnerSize(state::DEFAULT_WINDOW_SIZE.into()));
+    state.log(LogLevel::Success, tr(state.language, "session.reset"));
+}
+
+// --- Job Files ---
+
+const JOBS_DIR: &str = "jobs";
+
+fn job_file_dialog(state: &AppState) -> rfd::FileDialog {
+    rfd::FileDialog::new()
+        .add_filter(tr(state.language, "file.filter_job"), &["json"])
+        .set_directory(JOBS_DIR)
+}
+
+fn display_file_name(path: &Path) -> String {
+    path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned())
+}
+
+/// File name, or the job name while it has no file; with a star while there are unsaved changes.
+pub fn tab_title(tab: &JobTab) -> String {
+    let name = tab.file.as_deref().map_or_else(|| tab.job.name.clone(), display_file_name);
+    format!("{}{}", name, if tab.dirty { "*" } else { "" })
+}
+
+/// "name.json* - Vouwbank Simulator" for the active job.
+pub fn window_title(state: &AppState) -> String {
+    let file_name = state.tab().file.as_deref().map_or_else(|| tr(state.language, "app.untitled").to_string(), display_file_name);
+    format!("{}{} - Vouwbank Simulator", file_name, if state.tab().dirty { "*" } else { "" })
+}
+
+/// Puts the failure in the status bar and opens the error dialog with the full cause.
+fn report_storage_error(state: &mut AppState, action_key: &'static str, path: &Path, error: &db::JobStorageError) {
+    let lang = state.language;
+    let action = tr(lang, action_key);
+    let mut cause = i18n::storage_error(lang, error);
+    state.log(LogLevel::Error, format!("{}: {} ({})", action, cause, path.display()));
+    // The translated text already carries the direct cause; list only what lies beneath it
+    let mut source = std::error::Error::source(error).and_then(|inner| inner.source());
+    while let Some(inner) = source {
+        cause.push_str("\n  ");
+        cause.push_str(&trf(lang, "file.cause", &[&inner]));
+        source = inner.source();
+    }
+    state.storage_error = Some(StorageErrorReport { action: action.to_string(), path: path.to_path_buf(), cause });
+}
+
+/// Writes the job to `file_path`; returns whether it was saved.
+pub fn handle_save_job(state: &mut AppState, file_path: Option<PathBuf>) -> bool {
+    let lang = state.language;
+    let Some(path) = file_path else {
+        state.log(LogLevel::Info, tr(lang, "file.save_cancelled"));
+        return false;
+    };
+    match db::save_job_to_file(&state.tab().job, &path) {
+        Ok(()) => {
+            state.log(LogLevel::Success, trf(lang, "file.saved", &[&state.tab().job.name, &path.display()]));
+            remember_recent_file(state, &path);
+            state.tab_mut().file = Some(path);
+            state.tab_mut().dirty = false;
+            true
+        }
+        Err(e) => {
+            report_storage_error(state, "file.save_failed", &path, &e);
+            false
+        }
+    }
+}
+
+pub fn handle_load_job(state: &mut AppState, file_path: Option<PathBuf>) {
+    let lang = state.language;
+    let Some(path) = file_path else {
+        state.log(LogLevel::Info, tr(lang, "file.load_cancelled"));
+        return;
+    };
+    if let Some(index) = state.tabs.iter().position(|tab| tab.file.as_ref() == Some(&path)) {
+        switch_tab(state, index);
+        state.log(LogLevel::Info, trf(lang, "file.already_open", &[&path.display()]));
+        return;
+    }
+    match db::load_job_from_file(&path) {
+        Ok(loaded_job) => {
+            // Clear bend input fields or populate from first loaded bend? For now, clear.
+            state.bend_input = BendInputState::default();
+            let tab = JobTab { profile_load_status: "profile.outdated_loaded", ..JobTab::new(loaded_job, Some(path.clone())) };
+            open_tab(state, tab);
+            state.log(LogLevel::Success, trf(lang, "file.loaded", &[&path.display()]));
+            remember_recent_file(state, &path);
+        }
+        Err(e) => {
+            if matches!(e, db::JobStorageError::NotFound(_)) {
+                state.recent_files.retain(|p| *p != path); // Moved or deleted since
+            }
+            report_storage_error(state, "file.load_failed", &path, &e);
+        }
+    }
+}
+
+fn remember_recent_file(state: &mut AppState, path: &Path) {
+    state.recent_files.retain(|p| p != path);
+    state.recent_files.insert(0, path.to_path_buf());
+    state.recent_files.truncate(state::MAX_RECENT_FILES);
+}
+
+// --- Job Tabs ---
+
+fn new_job(state: &mut AppState) {
+    state.tabs.push(JobTab::new(Job::default(), None));
+    state.active_tab = state.tabs.len() - 1;
+    sync_sheet_input_from_job(state);
+    state.log(LogLevel::Info, tr(state.language, "file.new_job"));
+}
+
+/// Makes `tab` the active tab; it takes the place of an untouched new job.
+fn open_tab(state: &mut AppState, tab: JobTab) {
+    if state.tab().is_pristine() {
+        *state.tab_mut() = tab;
     } else {
-        println!("Save job cancelled."); // Update state.status_message
+        state.tabs.push(tab);
+        state.active_tab = state.tabs.len() - 1;
     }
+    sync_sheet_input_from_job(state);
 }
 
-pub fn handle_load_job(state: &mut AppState, file_path: Option<String>) {
-    if let Some(path) = file_path {
-        match db::load_job_from_file(&path) {
-            Ok(loaded_job) => {
-                state.current_job = loaded_job;
-                // Update input fields from loaded job
-                state.sheet_input.length_mm_str = state.current_job.sheet.original_length_mm.to_string();
-                state.sheet_input.thickness_mm_str = state.current_job.sheet.thickness_mm.to_string();
-                state.sheet_input.width_mm_str = state.current_job.sheet.width_mm.to_string();
-                state.sheet_input.selected_material_idx = state.material_display_order.iter().position(|n| *n == state.current_job.sheet.material_name).unwrap_or(0);
-                // Clear bend input fields or populate from first loaded bend? For now, clear.
-                state.bend_input = BendInputState::default();
-                state.simulated_profile_texture = None; // Clear old profile
-                state.profile_load_status = "New job loaded, profile outdated.".to_string();
-                println!("Job loaded from '{}'", path); // Update state.status_message
+pub fn switch_tab(state: &mut AppState, index: usize) {
+    if index < state.tabs.len() && index != state.active_tab {
+        state.active_tab = index;
+        sync_sheet_input_from_job(state);
+    }
+}
+
+/// Closes the active tab; closing the last one leaves a new job.
+fn close_job(state: &mut AppState) {
+    let closed = state.tabs.remove(state.active_tab);
+    if state.tabs.is_empty() {
+        state.tabs.push(JobTab::new(Job::default(), None));
+    }
+    state.active_tab = state.active_tab.min(state.tabs.len() - 1);
+    sync_sheet_input_from_job(state);
+    state.log(LogLevel::Info, trf(state.language, "file.closed", &[&closed.job.name]));
+}
+
+/// Closes every job with unsaved changes, so exiting after "Discard" doesn't
+/// bring them back with the session.
+fn discard_unsaved_jobs(state: &mut AppState) {
+    state.tabs.retain(|tab| !tab.dirty);
+    if state.tabs.is_empty() {
+        state.tabs.push(JobTab::new(Job::default(), None));
+    }
+    state.active_tab = state.active_tab.min(state.tabs.len() - 1);
+    sync_sheet_input_from_job(state);
+}
+
+/// Saves every job with unsaved changes; stops at the first that isn't saved.
+fn save_all_jobs(state: &mut AppState) -> bool {
+    for index in 0..state.tabs.len() {
+        if state.tabs[index].dirty {
+            switch_tab(state, index);
+            if !save_job(state) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Saves to the current file, or asks for one when the job was never saved.
+pub fn save_job(state: &mut AppState) -> bool {
+    match state.tab().file.clone() {
+        Some(path) => handle_save_job(state, Some(path)),
+        None => save_job_as(state),
+    }
+}
+
+pub fn save_job_as(state: &mut AppState) -> bool {
+    let path = job_file_dialog(state)
+        .set_file_name(format!("{}.json", state.tab().job.name))
+        .save_file();
+    handle_save_job(state, path)
+}
+
+fn perform_job_action(ctx: &Context, state: &mut AppState, action: PendingJobAction) {
+    match action {
+        PendingJobAction::NewJob => new_job(state),
+        PendingJobAction::OpenJob => {
+            let path = job_file_dialog(state).pick_file();
+            handle_load_job(state, path);
+        }
+        PendingJobAction::OpenFile(path) => handle_load_job(state, Some(path)),
+        PendingJobAction::CloseJob => close_job(state),
+        PendingJobAction::Exit => {
+            state.close_confirmed = true;
+            ctx.send_viewport_cmd(ViewportCommand::Close);
+        }
+    }
+}
+
+/// Runs the action, or holds it behind the "unsaved changes" prompt if it would
+/// drop unsaved changes. Exiting shows the first job with changes.
+pub fn request_job_action(ctx: &Context, state: &mut AppState, action: PendingJobAction) {
+    let needs_prompt = match action {
+        PendingJobAction::CloseJob => state.tab().dirty,
+        PendingJobAction::Exit => match state.tabs.iter().position(|tab| tab.dirty) {
+            Some(index) => { switch_tab(state, index); true }
+            None => false,
+        },
+        PendingJobAction::NewJob | PendingJobAction::OpenJob | PendingJobAction::OpenFile(_) => false, // Opens a tab
+    };
+    if needs_prompt {
+        state.pending_job_action = Some(action);
+    } else {
+        perform_job_action(ctx, state, action);
+    }
+}
+
+pub fn resolve_pending_job_action(ctx: &Context, state: &mut AppState, choice: UnsavedChangesChoice) {
+    let Some(action) = state.pending_job_action.take() else { return };
+    let saved = |state: &mut AppState| match action {
+        PendingJobAction::Exit => save_all_jobs(state),
+        _ => save_job(state),
+    };
+    match choice {
+        UnsavedChangesChoice::Save if !saved(state) => {} // Save failed or cancelled: keep the job open
+        UnsavedChangesChoice::Discard if action == PendingJobAction::Exit => {
+            discard_unsaved_jobs(state);
+            perform_job_action(ctx, state, action);
+        }
+        UnsavedChangesChoice::Save | UnsavedChangesChoice::Discard => perform_job_action(ctx, state, action),
+        UnsavedChangesChoice::Cancel => state.log(LogLevel::Info, tr(state.language, "file.action_cancelled")),
+    }
+}
+
+// --- Production ---
+
+/// Queues the active job with the quantity, due date and customer from the form.
+pub fn add_production_order(state: &mut AppState) {
+    let lang = state.language;
+    let form = &state.production;
+    let Ok(quantity) = form.quantity.trim().parse::<u32>() else {
+        state.log(LogLevel::Error, trf(lang, "production.invalid_quantity", &[&form.quantity]));
+        return;
+    };
+    let Ok(due_date) = chrono::NaiveDate::parse_from_str(form.due_date.trim(), state::DATE_FORMAT) else {
+        state.log(LogLevel::Error, trf(lang, "production.invalid_date", &[&form.due_date]));
+        return;
+    };
+    let tab = &state.tabs[state.active_tab];
+    let customer = state.production.customer.clone();
+    let added = state.production.queue.add_order(&customer, &tab.job.name, tab.file.clone(), quantity, due_date, chrono::Local::now());
+    match added {
+        Ok(id) => {
+            let message = trf(lang, "production.order_added", &[&id, &quantity, &tab.job.name]);
+            if tab.file.is_none() {
+                state.log(LogLevel::Warning, tr(lang, "production.job_not_saved"));
             }
-            Err(e) => eprintln!("Failed to load job: {}", e), // Update state.status_message
+            state.log(LogLevel::Success, message);
+        }
+        Err(e) => state.log(LogLevel::Error, i18n::production_error(lang, &e)),
+    }
+}
+
+pub fn mark_parts_bent(state: &mut AppState, order_id: u64, count: u32) {
+    let lang = state.language;
+    match state.production.queue.record_bent(order_id, count, chrono::Local::now()) {
+        Ok(()) => {
+            let queue = &state.production.queue;
+            let message = match queue.order(order_id) {
+                Some(order) if queue.is_complete(order) => trf(lang, "production.order_complete", &[&order_id, &order.quantity]),
+                _ => trf(lang, "production.parts_marked", &[&count, &order_id]),
+            };
+            state.log(LogLevel::Success, message);
         }
+        Err(e) => state.log(LogLevel::Error, i18n::production_error(lang, &e)),
+    }
+}
+
+pub fn remove_production_order(state: &mut AppState, order_id: u64) {
+    if let Some(order) = state.production.queue.remove_order(order_id) {
+        state.log(LogLevel::Info, trf(state.language, "production.order_removed", &[&order.id, &order.job_name]));
+    }
+}
+
+// --- Quoting ---
+
+/// Quote for the active job with the selected tools and the quote window's rates.
+pub fn current_quote(state: &AppState) -> Option<Quote> {
+    let setup = machine_setup(state)?;
+    let cycle = cycle_time::estimate(&setup, &state.tab().job, &state.machine_speeds);
+    Some(quote::quote(&setup, &state.tab().job, cycle.total_s(), &state.quote.rates, state.quote.quantity))
+}
+
+/// The quote as translated "label: value" lines, for the PDF.
+pub fn quote_lines(state: &AppState, quote: &Quote) -> Vec<String> {
+    let (lang, unit) = (state.language, state.length_unit);
+    let sheet = &state.tab().job.sheet;
+    let money = |amount: f64| format!("{:.2} {}", amount, quote.currency);
+    vec![
+        trf(lang, "quote.line_date", &[&chrono::Local::now().format(state::DATE_FORMAT)]),
+        trf(lang, "quote.line_job", &[&quote.job_name]),
+        trf(lang, "quote.line_sheet", &[
+            &i18n::material_name(lang, &sheet.material_name),
+            &unit.format(sheet.original_length_mm, 1), &unit.format(sheet.width_mm, 1), &unit.format(sheet.thickness_mm, 2),
+        ]),
+        trf(lang, "quote.line_bends", &[&state.tab().job.steps.len()]),
+        String::new(),
+        trf(lang, "quote.line_weight", &[&format!("{:.3}", quote.weight_kg), &money(quote.price_per_kg)]),
+        trf(lang, "quote.line_material", &[&money(quote.material_cost)]),
+        trf(lang, "quote.line_cycle", &[&format!("{:.1}", quote.cycle_time_s), &money(state.quote.rates.machine_rate_per_hour)]),
+        trf(lang, "quote.line_machine", &[&money(quote.machine_cost)]),
+        trf(lang, "quote.line_setup", &[&format!("{:.0}", state.quote.rates.setup_minutes), &money(quote.setup_cost)]),
+        String::new(),
+        trf(lang, "quote.line_quantity", &[&quote.quantity]),
+        trf(lang, "quote.line_unit_price", &[&money(quote.unit_price())]),
+        trf(lang, "quote.line_batch_total", &[&money(quote.batch_total())]),
+    ]
+}
+
+/// Asks for a file and writes the quote as CSV or PDF.
+pub fn export_quote(state: &mut AppState, pdf: bool) {
+    let lang = state.language;
+    let Some(quote) = current_quote(state) else {
+        state.log(LogLevel::Error, tr(lang, "tooling.none_selected"));
+        return;
+    };
+    let (filter, extension) = if pdf { ("quote.filter_pdf", "pdf") } else { ("quote.filter_csv", "csv") };
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter(tr(lang, filter), &[extension])
+        .set_file_name(format!("{}-quote.{}", quote.job_name, extension))
+        .save_file()
+    else {
+        return;
+    };
+    let contents = if pdf {
+        export::text_pdf(&trf(lang, "quote.title_for", &[&quote.job_name]), &quote_lines(state, &quote))
     } else {
-        println!("Load job cancelled."); // Update state.status_message
+        quote::to_csv(&quote).into_bytes()
+    };
+    match std::fs::write(&path, contents) {
+        Ok(()) => state.log(LogLevel::Success, trf(lang, "quote.exported", &[&path.display()])),
+        Err(e) => state.log(LogLevel::Error, trf(lang, "quote.export_failed", &[&path.display(), &e])),
     }
 }
 
+// --- Nesting ---
+
+/// Adds the active job's blank to the nest, or more of it when it is already there.
+pub fn add_job_to_nest(state: &mut AppState) {
+    let blank = Blank::from_job(&state.tab().job, state.nest.quantity);
+    let same = |b: &&mut Blank| b.job_name == blank.job_name && b.material_name == blank.material_name
+        && b.thickness_mm == blank.thickness_mm && b.length_mm == blank.length_mm && b.width_mm == blank.width_mm
+        && b.rolling_direction == blank.rolling_direction;
+    match state.nest.blanks.iter_mut().find(same) {
+        Some(existing) => existing.quantity += blank.quantity,
+        None => state.nest.blanks.push(blank),
+    }
+}
+
+pub fn current_nest(state: &AppState) -> Result<Nest, NestError> {
+    let plate = nesting::standard_plates().get(state.nest.plate_idx).copied().unwrap_or(nesting::standard_plates()[0]);
+    nesting::nest(state.nest.blanks.clone(), plate, state.nest.spacing_mm)
+}
+
+/// Writes one DXF per plate into a folder the user picks.
+pub fn export_nest_dxf(state: &mut AppState) {
+    let lang = state.language;
+    let nest = match current_nest(state) {
+        Ok(nest) => nest,
+        Err(e) => {
+            state.log(LogLevel::Error, i18n::nest_error(lang, &e));
+            return;
+        }
+    };
+    let Some(dir) = rfd::FileDialog::new().set_title(tr(lang, "nest.export_dxf")).pick_folder() else { return };
+    for (i, plate) in nest.plates.iter().enumerate() {
+        let stock = format!("{}-{}mm", plate.material_name, plate.thickness_mm).replace(|c: char| !c.is_ascii_alphanumeric() && c != '.', "-");
+        let path = dir.join(format!("nest-{}-{}.dxf", stock, i + 1));
+        match std::fs::write(&path, export::nest_dxf(&nest, i)) {
+            Ok(()) => state.log(LogLevel::Success, trf(lang, "nest.exported", &[&path.display()])),
+            Err(e) => {
+                state.log(LogLevel::Error, trf(lang, "nest.export_failed", &[&path.display(), &e]));
+                return;
+            }
+        }
+    }
+}
+
+// --- Message Log ---
+
+pub fn export_message_log(state: &mut AppState) {
+    let lang = state.language;
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter(tr(lang, "log.filter_text"), &["txt", "log"])
+        .set_file_name(format!("vouwbank-log-{}.txt", chrono::Local::now().format("%Y%m%d-%H%M%S")))
+        .save_file()
+    else {
+        return;
+    };
+    match state.message_log.export_to_file(&path) {
+        Ok(count) => state.log(LogLevel::Success, trf(lang, "log.exported", &[&count, &path.display()])),
+        Err(e) => state.log(LogLevel::Error, trf(lang, "log.export_failed", &[&path.display(), &e])),
+    }
+}
+
+// --- Job Library ---
+
+/// Rescans the library directory; every readable job gets its unvalidated profile as thumbnail.
+pub fn refresh_job_library(state: &mut AppState) {
+    state.job_library.entries.clear();
+    let files = match db::list_job_files(&state.job_library.directory_str) {
+        Ok(files) => files,
+        Err(e) => {
+            let dir = PathBuf::from(&state.job_library.directory_str);
+            report_storage_error(state, "library.read_failed", &dir, &e);
+            return;
+        }
+    };
+    let library = &mut state.job_library;
+    library.unreadable_files = 0;
+    for path in files {
+        match db::load_job_from_file(&path) {
+            Ok(job) => {
+                let profile = geometry::profile_polyline(&job, &geometry::expand_machine_steps(&job));
+                library.entries.push(JobLibraryEntry { path, job, profile });
+            }
+            Err(_) => library.unreadable_files += 1,
+        }
+    }
+    let message = trf(state.language, "library.found", &[&library.entries.len(), &library.directory_str]);
+    state.log(LogLevel::Info, message);
+}
 
+pub fn browse_job_library_directory(state: &mut AppState) {
+    if let Some(dir) = rfd::FileDialog::new().set_directory(&state.job_library.directory_str).pick_folder() {
+        state.job_library.directory_str = dir.display().to_string();
+        refresh_job_library(state);
+    }
+}
 
+/// Search matches job name or file name, case-insensitive.
+pub fn job_library_entry_matches(entry: &JobLibraryEntry, search: &str, material: Option<&MaterialName>) -> bool {
+    let search = search.trim().to_lowercase();
+    let file_name = display_file_name(&entry.path).to_lowercase();
+    (search.is_empty() || entry.job.name.to_lowercase().contains(&search) || file_name.contains(&search))
+        && material.is_none_or(|m| *m == entry.job.sheet.material_name)
+}
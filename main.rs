@@ -2,20 +2,17 @@ mod app;
 mod state;
 mod ui;
 mod logic;
-mod db;
+mod message_log;
+mod i18n;
+mod numeric_input;
+mod assets;
 
 use eframe::egui;
 
 fn main() -> Result<(), eframe::Error> {
-    // Ensure the assets directory is correctly located relative to the executable
-    // This might be important for release builds if assets are not embedded.
-    // For development with `cargo run`, it usually finds `assets/` in the project root.
-    println!("Current working directory: {:?}", std::env::current_dir().unwrap_or_default());
-    println!("Attempting to load image from: assets/drawing.png");
-
-
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_inner_size([800.0, 600.0]),
+        viewport: egui::ViewportBuilder::default().with_inner_size(state::DEFAULT_WINDOW_SIZE),
+        persist_window: true, // Reopen with the last size and position
         ..Default::default()
     };
 
@@ -0,0 +1,172 @@
+// src/message_log.rs
+use egui::Color32;
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+
+pub const MAX_LOG_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    pub fn all() -> Vec<Self> {
+        vec![LogLevel::Debug, LogLevel::Info, LogLevel::Success, LogLevel::Warning, LogLevel::Error]
+    }
+
+    /// Text colour in the status bar and log panel; `None` uses the theme colour.
+    pub fn color(&self) -> Option<Color32> {
+        match self {
+            LogLevel::Debug => Some(Color32::GRAY),
+            LogLevel::Info => None,
+            LogLevel::Success => Some(Color32::GREEN),
+            LogLevel::Warning => Some(Color32::YELLOW),
+            LogLevel::Error => Some(Color32::RED),
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogLevel::Debug => write!(f, "DEBUG"),
+            LogLevel::Info => write!(f, "INFO"),
+            LogLevel::Success => write!(f, "OK"),
+            LogLevel::Warning => write!(f, "WARN"),
+            LogLevel::Error => write!(f, "ERROR"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: chrono::DateTime<chrono::Local>,
+    pub level: LogLevel,
+    pub text: String,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}] {}", self.time.format("%Y-%m-%d %H:%M:%S"), self.level, self.text)
+    }
+}
+
+/// Every message the application reported, oldest first. The status bar shows
+/// the latest entry; the log panel shows the entries passing the filters.
+pub struct MessageLog {
+    pub entries: Vec<LogEntry>,
+    pub shown_levels: Vec<LogLevel>, // Level filter of the log panel
+    pub search: String,
+}
+
+impl Default for MessageLog {
+    fn default() -> Self {
+        MessageLog {
+            entries: Vec::new(),
+            shown_levels: vec![LogLevel::Info, LogLevel::Success, LogLevel::Warning, LogLevel::Error],
+            search: String::new(),
+        }
+    }
+}
+
+impl MessageLog {
+    pub fn push(&mut self, level: LogLevel, text: impl Into<String>) {
+        self.entries.push(LogEntry { time: chrono::Local::now(), level, text: text.into() });
+        if self.entries.len() > MAX_LOG_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Latest entry worth showing in the status bar (debug output is skipped).
+    pub fn latest(&self) -> Option<&LogEntry> {
+        self.entries.iter().rev().find(|e| e.level != LogLevel::Debug)
+    }
+
+    pub fn is_visible(&self, entry: &LogEntry) -> bool {
+        let search = self.search.trim().to_lowercase();
+        self.shown_levels.contains(&entry.level) && (search.is_empty() || entry.text.to_lowercase().contains(&search))
+    }
+
+    pub fn visible_entries(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter().filter(|e| self.is_visible(e))
+    }
+
+    /// Writes the entries passing the current filters as plain text, one per line.
+    pub fn export_to_file(&self, path: &Path) -> std::io::Result<usize> {
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        let mut count = 0;
+        for entry in self.visible_entries() {
+            writeln!(file, "{}", entry)?;
+            count += 1;
+        }
+        file.flush()?;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oldest_entries_are_evicted() {
+        let mut log = MessageLog::default();
+        for i in 0..MAX_LOG_ENTRIES + 5 {
+            log.push(LogLevel::Info, format!("message {}", i));
+        }
+        assert_eq!(log.entries.len(), MAX_LOG_ENTRIES);
+        assert_eq!(log.entries[0].text, "message 5");
+        assert_eq!(log.entries.last().unwrap().text, format!("message {}", MAX_LOG_ENTRIES + 4));
+    }
+
+    #[test]
+    fn latest_skips_debug_output() {
+        let mut log = MessageLog::default();
+        assert!(log.latest().is_none());
+        log.push(LogLevel::Warning, "flange too short");
+        log.push(LogLevel::Debug, "redraw");
+        assert_eq!(log.latest().unwrap().text, "flange too short");
+    }
+
+    #[test]
+    fn filters_by_level_and_search() {
+        let mut log = MessageLog::default();
+        log.push(LogLevel::Debug, "Saved job draft");
+        log.push(LogLevel::Success, "Saved job Bracket");
+        log.push(LogLevel::Error, "Failed to load job");
+        assert!(!log.is_visible(&log.entries[0])); // Debug is hidden by default
+        assert_eq!(log.visible_entries().count(), 2);
+
+        log.search = "  SAVED ".to_string();
+        let visible: Vec<&str> = log.visible_entries().map(|e| e.text.as_str()).collect();
+        assert_eq!(visible, vec!["Saved job Bracket"]);
+
+        log.shown_levels.push(LogLevel::Debug);
+        assert_eq!(log.visible_entries().count(), 2);
+    }
+
+    #[test]
+    fn export_writes_only_the_filtered_entries() {
+        let mut log = MessageLog::default();
+        log.push(LogLevel::Info, "Opened job Bracket");
+        log.push(LogLevel::Warning, "Part tips away from the backgauge");
+        log.push(LogLevel::Error, "Press capacity exceeded");
+        log.shown_levels = vec![LogLevel::Warning, LogLevel::Error];
+        log.search = "press".to_string();
+
+        let path = std::env::temp_dir().join(format!("vouwbank-log-test-{}.txt", std::process::id()));
+        let count = log.export_to_file(&path).unwrap();
+        let contents = std::fs::read_to_string(&path);
+        let _ = std::fs::remove_file(&path);
+        let contents = contents.unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(contents.lines().count(), 1);
+        assert!(contents.ends_with("[ERROR] Press capacity exceeded\n"), "{}", contents);
+    }
+}
@@ -0,0 +1,318 @@
+// src/model.rs
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap; // For materials map
+use std::fmt;
+
+// --- CNC Specific Enums and Structs ---
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)] // Added Hash for HashMap key
+pub enum MaterialName {
+    Steel,
+    Aluminum,
+    StainlessSteel,
+    Copper,
+    MildSteel,
+    Custom(String), // Allow custom material names
+}
+
+impl MaterialName {
+    pub fn default_names() -> Vec<Self> {
+        vec![
+            MaterialName::Steel,
+            MaterialName::Aluminum,
+            MaterialName::StainlessSteel,
+            MaterialName::Copper,
+            MaterialName::MildSteel,
+        ]
+    }
+    // You might want a FromStr implementation too for parsing user input
+}
+
+impl fmt::Display for MaterialName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MaterialName::Steel => write!(f, "Steel"),
+            MaterialName::Aluminum => write!(f, "Aluminum"),
+            MaterialName::StainlessSteel => write!(f, "Stainless Steel"),
+            MaterialName::Copper => write!(f, "Copper"),
+            MaterialName::MildSteel => write!(f, "Mild Steel"),
+            MaterialName::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaterialDetails {
+    pub name: MaterialName,
+    pub density_kg_m3: f64,    // kg/m^3
+    pub yield_stress_mpa: f64, // MPa
+    pub tensile_modulus_gpa: f64, // GPa (Young's Modulus)
+    pub min_bend_radius_factor: f64, // Factor times thickness, bend line across the rolling direction
+    pub min_bend_radius_factor_parallel: f64, // Bend line along the rolling direction, where it cracks sooner
+    pub price_per_kg: f64, // Sheet price for quotes, in the quote currency
+}
+
+impl MaterialDetails {
+    /// Smallest inner radius the material takes without cracking.
+    pub fn recommended_min_bend_radius_mm(&self, thickness_mm: f64) -> f64 {
+        if thickness_mm <= 0.0 { return 0.0; }
+        if self.min_bend_radius_factor <= 0.0 {
+            thickness_mm * 0.5 // Default fallback
+        } else {
+            thickness_mm * self.min_bend_radius_factor
+        }
+    }
+
+    /// Smallest inner radius for bend lines in a sheet rolled in `rolling`. An
+    /// unknown rolling direction gets the usual (across the grain) minimum.
+    pub fn min_bend_radius_mm(&self, thickness_mm: f64, rolling: RollingDirection) -> f64 {
+        let across = self.recommended_min_bend_radius_mm(thickness_mm);
+        if rolling.bend_lines_parallel() {
+            across.max(thickness_mm * self.min_bend_radius_factor_parallel)
+        } else {
+            across
+        }
+    }
+}
+
+/// Direction the sheet was rolled in at the mill, relative to the blank. Bend
+/// lines run across the blank's length, along its width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RollingDirection {
+    #[default]
+    Unknown,
+    AlongLength, // Bend lines across the grain
+    AlongWidth,  // Bend lines along the grain
+}
+
+impl RollingDirection {
+    pub fn all() -> Vec<Self> {
+        vec![RollingDirection::Unknown, RollingDirection::AlongLength, RollingDirection::AlongWidth]
+    }
+
+    pub fn bend_lines_parallel(self) -> bool {
+        self == RollingDirection::AlongWidth
+    }
+}
+
+impl fmt::Display for RollingDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RollingDirection::Unknown => write!(f, "Unknown"),
+            RollingDirection::AlongLength => write!(f, "Along length"),
+            RollingDirection::AlongWidth => write!(f, "Along width"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BendDirection {
+    Up,
+    Down,
+}
+
+impl BendDirection {
+    pub fn default_directions() -> Vec<Self> {
+        vec![BendDirection::Up, BendDirection::Down]
+    }
+}
+
+impl fmt::Display for BendDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BendDirection::Up => write!(f, "Up"),
+            BendDirection::Down => write!(f, "Down"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BendMethod {
+    #[default]
+    AirBending,
+    Bottoming,
+    Coining,
+    Hemming, // Flattening of a pre-bent flange
+}
+
+impl BendMethod {
+    pub fn default_methods() -> Vec<Self> {
+        vec![BendMethod::AirBending, BendMethod::Bottoming, BendMethod::Coining, BendMethod::Hemming]
+    }
+}
+
+impl fmt::Display for BendMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BendMethod::AirBending => write!(f, "Air Bending"),
+            BendMethod::Bottoming => write!(f, "Bottoming"),
+            BendMethod::Coining => write!(f, "Coining"),
+            BendMethod::Hemming => write!(f, "Hemming"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HemStyle {
+    Open,
+    Closed,
+    Teardrop,
+}
+
+/// What a sequence entry forms. Compound features expand into several machine
+/// hits (see `geometry::expand_feature`) but stay one entry in the job.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum BendFeature {
+    #[default]
+    Single,
+    Hem(HemStyle),
+    Joggle { offset_mm: f64 }, // Z-offset: two opposite bends, offset height between the flanges
+    Bump { hits: usize, pitch_mm: f64, hit_radius_mm: f64 }, // Large radius formed by many small air bends
+}
+
+impl fmt::Display for BendFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BendFeature::Single => write!(f, "Single"),
+            BendFeature::Hem(HemStyle::Open) => write!(f, "Hem (open)"),
+            BendFeature::Hem(HemStyle::Closed) => write!(f, "Hem (closed)"),
+            BendFeature::Hem(HemStyle::Teardrop) => write!(f, "Hem (teardrop)"),
+            BendFeature::Joggle { offset_mm } => write!(f, "Joggle {:.1}mm", offset_mm),
+            BendFeature::Bump { hits, pitch_mm, .. } => write!(f, "Bump {}× {:.1}mm", hits, pitch_mm),
+        }
+    }
+}
+
+/// A feature without its dimensions, as picked when adding a step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BendFeatureKind {
+    #[default]
+    Single,
+    HemOpen,
+    HemClosed,
+    HemTeardrop,
+    Joggle,
+    Bump,
+}
+
+impl BendFeatureKind {
+    pub fn all() -> Vec<Self> {
+        vec![
+            BendFeatureKind::Single,
+            BendFeatureKind::HemOpen,
+            BendFeatureKind::HemClosed,
+            BendFeatureKind::HemTeardrop,
+            BendFeatureKind::Joggle,
+            BendFeatureKind::Bump,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BendStep {
+    pub sequence_order: usize, // 1-based
+    pub position_mm: f64,
+    pub target_angle_deg: f64,
+    pub radius_mm: f64,
+    pub direction: BendDirection,
+    pub method: BendMethod,
+    pub feature: BendFeature,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SheetMetal {
+    pub id: String,
+    pub original_length_mm: f64,
+    pub thickness_mm: f64,
+    pub width_mm: f64,
+    pub material_name: MaterialName, // Store by name, lookup details in the materials library
+    pub rolling_direction: RollingDirection,
+    // CurrentBends would be part of a "ProcessedSheet" or similar,
+    // or the simulation would directly modify a visual representation.
+    // For now, let's assume the BendSteps in the Job define the target.
+}
+
+impl Default for SheetMetal {
+    fn default() -> Self {
+        SheetMetal {
+            id: "DefaultSheet-001".to_string(),
+            original_length_mm: 300.0,
+            thickness_mm: 2.0,
+            width_mm: 100.0,
+            material_name: MaterialName::Steel,
+            rolling_direction: RollingDirection::Unknown,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Punch {
+    pub name: String,
+    pub height_mm: f64,
+    pub angle_deg: f64,
+    pub radius_mm: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Die {
+    pub name: String,
+    pub v_opening_mm: f64,
+    pub angle_deg: f64,
+    pub shoulder_radius_mm: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    pub name: String,
+    pub sheet: SheetMetal, // The workpiece definition for this job
+    pub steps: Vec<BendStep>,
+}
+
+impl Default for Job {
+    fn default() -> Self {
+        Job {
+            name: "DefaultJob-001".to_string(),
+            sheet: SheetMetal::default(),
+            steps: Vec::new(),
+        }
+    }
+}
+
+/// Everything a hit is checked against: the workpiece and the tools in the machine.
+#[derive(Debug, Clone, Copy)]
+pub struct MachineSetup<'a> {
+    pub sheet: &'a SheetMetal,
+    pub material: &'a MaterialDetails,
+    pub punch: &'a Punch,
+    pub die: &'a Die,
+}
+
+// --- Default Library ---
+
+pub fn default_materials() -> HashMap<MaterialName, MaterialDetails> {
+    let mut materials = HashMap::new();
+    materials.insert(MaterialName::Steel, MaterialDetails { name: MaterialName::Steel, density_kg_m3: 7850.0, yield_stress_mpa: 250.0, tensile_modulus_gpa: 200.0, min_bend_radius_factor: 1.5, min_bend_radius_factor_parallel: 2.5, price_per_kg: 1.10 });
+    materials.insert(MaterialName::Aluminum, MaterialDetails { name: MaterialName::Aluminum, density_kg_m3: 2700.0, yield_stress_mpa: 100.0, tensile_modulus_gpa: 70.0, min_bend_radius_factor: 1.0, min_bend_radius_factor_parallel: 2.0, price_per_kg: 4.50 });
+    materials.insert(MaterialName::StainlessSteel, MaterialDetails { name: MaterialName::StainlessSteel, density_kg_m3: 8000.0, yield_stress_mpa: 215.0, tensile_modulus_gpa: 193.0, min_bend_radius_factor: 2.0, min_bend_radius_factor_parallel: 3.0, price_per_kg: 4.20 });
+    materials.insert(MaterialName::Copper, MaterialDetails { name: MaterialName::Copper, density_kg_m3: 8960.0, yield_stress_mpa: 70.0, tensile_modulus_gpa: 117.0, min_bend_radius_factor: 0.8, min_bend_radius_factor_parallel: 1.0, price_per_kg: 10.50 });
+    materials.insert(MaterialName::MildSteel, MaterialDetails { name: MaterialName::MildSteel, density_kg_m3: 7850.0, yield_stress_mpa: 220.0, tensile_modulus_gpa: 200.0, min_bend_radius_factor: 1.2, min_bend_radius_factor_parallel: 2.0, price_per_kg: 1.00 });
+    materials
+}
+
+pub fn default_punches() -> Vec<Punch> {
+    vec![
+        Punch { name: "P88.10.R06".to_string(), height_mm: 60.0, angle_deg: 88.0, radius_mm: 0.6 },
+        Punch { name: "P30.15.R1".to_string(), height_mm: 65.0, angle_deg: 30.0, radius_mm: 1.0 },
+        Punch { name: "Default Punch".to_string(), height_mm: 50.0, angle_deg: 90.0, radius_mm: 1.0 },
+    ]
+}
+
+pub fn default_dies() -> Vec<Die> {
+    vec![
+        Die { name: "D12.90.R2".to_string(), v_opening_mm: 12.0, angle_deg: 90.0, shoulder_radius_mm: 2.0 },
+        Die { name: "D20.60.R3".to_string(), v_opening_mm: 20.0, angle_deg: 60.0, shoulder_radius_mm: 3.0 },
+        Die { name: "D16.30.R2".to_string(), v_opening_mm: 16.0, angle_deg: 30.0, shoulder_radius_mm: 2.0 },
+        Die { name: "Default Die".to_string(), v_opening_mm: 16.0, angle_deg: 90.0, shoulder_radius_mm: 2.0 },
+    ]
+}
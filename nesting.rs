@@ -0,0 +1,350 @@
+// src/nesting.rs
+// Nesting flat blanks onto standard plates for the laser: rectangular blanks
+// in rows (shelves), with a gap between parts and along the plate edges.
+// Only blanks of the same material and thickness share a plate, and blanks with
+// a known rolling direction keep it along the plate length, the way plates are rolled.
+use crate::model::{Job, MaterialName, RollingDirection};
+use std::fmt;
+
+pub const DEFAULT_PART_SPACING_MM: f64 = 10.0; // Kerf, micro-joints and the edge trim
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlateSize {
+    pub length_mm: f64, // Along the plate's long side, x in the layout
+    pub width_mm: f64,
+}
+
+impl PlateSize {
+    pub fn area_mm2(&self) -> f64 {
+        self.length_mm * self.width_mm
+    }
+}
+
+impl fmt::Display for PlateSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}×{}", self.length_mm, self.width_mm)
+    }
+}
+
+/// Plate sizes stocked by most steel suppliers.
+pub fn standard_plates() -> Vec<PlateSize> {
+    vec![
+        PlateSize { length_mm: 2000.0, width_mm: 1000.0 },
+        PlateSize { length_mm: 2500.0, width_mm: 1250.0 },
+        PlateSize { length_mm: 3000.0, width_mm: 1500.0 },
+        PlateSize { length_mm: 4000.0, width_mm: 2000.0 },
+    ]
+}
+
+/// The flat blank of a job and how many to cut.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blank {
+    pub job_name: String,
+    pub material_name: MaterialName,
+    pub thickness_mm: f64,
+    pub length_mm: f64, // Developed length, across the bend lines
+    pub width_mm: f64,  // Along the bend lines
+    pub rolling_direction: RollingDirection,
+    pub quantity: u32,
+}
+
+impl Blank {
+    pub fn from_job(job: &Job, quantity: u32) -> Self {
+        Blank {
+            job_name: job.name.clone(),
+            material_name: job.sheet.material_name.clone(),
+            thickness_mm: job.sheet.thickness_mm,
+            length_mm: job.sheet.original_length_mm,
+            width_mm: job.sheet.width_mm,
+            rolling_direction: job.sheet.rolling_direction,
+            quantity,
+        }
+    }
+
+    pub fn area_mm2(&self) -> f64 {
+        self.length_mm * self.width_mm
+    }
+
+    /// Size along the plate length and width, placed either way round.
+    pub fn placed_size_mm(&self, rotated: bool) -> (f64, f64) {
+        if rotated { (self.width_mm, self.length_mm) } else { (self.length_mm, self.width_mm) }
+    }
+
+    /// Whether the blank may be placed with its length along the plate width.
+    fn may_rotate(&self, rotated: bool) -> bool {
+        match self.rolling_direction {
+            RollingDirection::Unknown => true,
+            RollingDirection::AlongLength => !rotated,
+            RollingDirection::AlongWidth => rotated,
+        }
+    }
+
+    fn same_stock(&self, other: &Blank) -> bool {
+        self.material_name == other.material_name && (self.thickness_mm - other.thickness_mm).abs() < 1e-6
+    }
+}
+
+/// One blank on a plate, lower left corner at (x, y).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement {
+    pub blank: usize, // Index into `Nest::blanks`
+    pub x_mm: f64,
+    pub y_mm: f64,
+    pub rotated: bool, // Blank length along the plate width
+}
+
+impl Placement {
+    /// Size of the placed blank along the plate length and width.
+    pub fn size_mm(&self, blank: &Blank) -> (f64, f64) {
+        blank.placed_size_mm(self.rotated)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NestedPlate {
+    pub material_name: MaterialName,
+    pub thickness_mm: f64,
+    pub placements: Vec<Placement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Nest {
+    pub plate: PlateSize,
+    pub spacing_mm: f64,
+    pub blanks: Vec<Blank>,
+    pub plates: Vec<NestedPlate>,
+}
+
+impl Nest {
+    /// Area of the blanks cut from one plate.
+    pub fn used_area_mm2(&self, plate: &NestedPlate) -> f64 {
+        plate.placements.iter().map(|p| self.blanks[p.blank].area_mm2()).sum()
+    }
+
+    pub fn plate_utilisation(&self, plate: &NestedPlate) -> f64 {
+        self.used_area_mm2(plate) / self.plate.area_mm2()
+    }
+
+    /// Blank area over the area of all plates used, 0..=1.
+    pub fn utilisation(&self) -> f64 {
+        if self.plates.is_empty() {
+            return 0.0;
+        }
+        let used: f64 = self.plates.iter().map(|p| self.used_area_mm2(p)).sum();
+        used / (self.plate.area_mm2() * self.plates.len() as f64)
+    }
+
+    /// Plate area left over as skeleton and offcuts.
+    pub fn scrap_area_mm2(&self) -> f64 {
+        self.plates.iter().map(|p| self.plate.area_mm2() - self.used_area_mm2(p)).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum NestError {
+    #[error("No blanks to nest")]
+    NoBlanks,
+    #[error("Blank of '{job_name}' does not fit on a {plate} plate")]
+    BlankTooLarge { job_name: String, plate: PlateSize },
+}
+
+/// A row of blanks across the plate length.
+struct Shelf {
+    plate: usize,
+    y_mm: f64,
+    height_mm: f64,
+    next_x_mm: f64,
+}
+
+/// Nests every blank `quantity` times, largest first, each into the first row
+/// with room (either way round, unless the rolling direction is known) before
+/// starting a new row or plate.
+pub fn nest(blanks: Vec<Blank>, plate: PlateSize, spacing_mm: f64) -> Result<Nest, NestError> {
+    let blanks: Vec<Blank> = blanks.into_iter().filter(|b| b.quantity > 0).collect();
+    if blanks.is_empty() {
+        return Err(NestError::NoBlanks);
+    }
+    let fits = |blank: &Blank, rotated: bool| {
+        let (length, width) = blank.placed_size_mm(rotated);
+        blank.may_rotate(rotated) && length + 2.0 * spacing_mm <= plate.length_mm && width + 2.0 * spacing_mm <= plate.width_mm
+    };
+    if let Some(blank) = blanks.iter().find(|b| !fits(b, false) && !fits(b, true)) {
+        return Err(NestError::BlankTooLarge { job_name: blank.job_name.clone(), plate });
+    }
+
+    let mut order: Vec<usize> = blanks.iter().enumerate().flat_map(|(i, b)| std::iter::repeat_n(i, b.quantity as usize)).collect();
+    order.sort_by(|&a, &b| {
+        let long_side = |b: &Blank| b.length_mm.max(b.width_mm);
+        long_side(&blanks[b]).total_cmp(&long_side(&blanks[a])).then(blanks[b].area_mm2().total_cmp(&blanks[a].area_mm2()))
+    });
+
+    let mut plates: Vec<NestedPlate> = Vec::new();
+    let mut shelves: Vec<Shelf> = Vec::new();
+    for index in order {
+        let blank = &blanks[index];
+        // Long side along the plate length first, so rows stay low
+        let long_side_rotated = blank.width_mm > blank.length_mm;
+        let orientations: Vec<(bool, (f64, f64))> = [long_side_rotated, !long_side_rotated]
+            .into_iter()
+            .filter(|rotated| fits(blank, *rotated))
+            .map(|rotated| (rotated, blank.placed_size_mm(rotated)))
+            .collect();
+
+        let in_shelf = shelves.iter().enumerate().find_map(|(s, shelf)| {
+            let stock = &blanks[plates[shelf.plate].placements[0].blank];
+            if !stock.same_stock(blank) {
+                return None;
+            }
+            orientations.iter().find(|(_, (length, width))| {
+                *width <= shelf.height_mm && shelf.next_x_mm + length + spacing_mm <= plate.length_mm
+            }).map(|&(rotated, size)| (s, rotated, size))
+        });
+        let (s, rotated, (length, _)) = match in_shelf {
+            Some(found) => found,
+            None => {
+                // A new row on the last plate of this stock with room for it either way round
+                let open_plate = plates.iter().enumerate().rev().find_map(|(p, nested)| {
+                    if !blanks[nested.placements[0].blank].same_stock(blank) {
+                        return None;
+                    }
+                    let top = shelves.iter().filter(|s| s.plate == p).map(|s| s.y_mm + s.height_mm).fold(0.0, f64::max);
+                    orientations.iter()
+                        .find(|(_, (_, width))| top + spacing_mm + width + spacing_mm <= plate.width_mm)
+                        .map(|&orientation| (p, top, orientation))
+                });
+                let (p, top, (rotated, (length, width))) = open_plate.unwrap_or_else(|| {
+                    plates.push(NestedPlate { material_name: blank.material_name.clone(), thickness_mm: blank.thickness_mm, placements: Vec::new() });
+                    (plates.len() - 1, 0.0, orientations[0]) // Not empty, checked above
+                });
+                shelves.push(Shelf { plate: p, y_mm: top + spacing_mm, height_mm: width, next_x_mm: spacing_mm });
+                (shelves.len() - 1, rotated, (length, width))
+            }
+        };
+        let shelf = &mut shelves[s];
+        plates[shelf.plate].placements.push(Placement { blank: index, x_mm: shelf.next_x_mm, y_mm: shelf.y_mm, rotated });
+        shelf.next_x_mm += length + spacing_mm;
+    }
+    Ok(Nest { plate, spacing_mm, blanks, plates })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLATE: PlateSize = PlateSize { length_mm: 2000.0, width_mm: 1000.0 };
+
+    fn blank(name: &str, length_mm: f64, width_mm: f64, quantity: u32) -> Blank {
+        Blank {
+            job_name: name.to_string(),
+            material_name: MaterialName::Steel,
+            thickness_mm: 2.0,
+            length_mm,
+            width_mm,
+            quantity,
+            rolling_direction: RollingDirection::Unknown,
+        }
+    }
+
+    /// Every placement inside the plate edges and `spacing` clear of the others.
+    fn assert_valid_layout(nest: &Nest) {
+        let s = nest.spacing_mm;
+        for plate in &nest.plates {
+            for (i, a) in plate.placements.iter().enumerate() {
+                let (al, aw) = a.size_mm(&nest.blanks[a.blank]);
+                assert!(a.x_mm >= s && a.y_mm >= s, "{:?} inside the edge trim", a);
+                assert!(a.x_mm + al + s <= nest.plate.length_mm + 1e-9 && a.y_mm + aw + s <= nest.plate.width_mm + 1e-9, "{:?} off the plate", a);
+                for b in &plate.placements[i + 1..] {
+                    let (bl, bw) = b.size_mm(&nest.blanks[b.blank]);
+                    let apart = a.x_mm + al + s <= b.x_mm + 1e-9 || b.x_mm + bl + s <= a.x_mm + 1e-9
+                        || a.y_mm + aw + s <= b.y_mm + 1e-9 || b.y_mm + bw + s <= a.y_mm + 1e-9;
+                    assert!(apart, "{:?} overlaps {:?}", a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn full_plate_has_no_scrap() {
+        let nest = nest(vec![blank("A", 1000.0, 500.0, 4)], PLATE, 0.0).unwrap();
+        assert_eq!(nest.plates.len(), 1);
+        assert!((nest.utilisation() - 1.0).abs() < 1e-9);
+        assert!(nest.scrap_area_mm2().abs() < 1e-6);
+        assert_valid_layout(&nest);
+    }
+
+    #[test]
+    fn utilisation_and_scrap_over_several_plates() {
+        let nest = nest(vec![blank("A", 1000.0, 500.0, 5)], PLATE, 0.0).unwrap();
+        assert_eq!(nest.plates.len(), 2);
+        assert!((nest.utilisation() - 5.0 / 8.0).abs() < 1e-9);
+        assert!((nest.scrap_area_mm2() - 1.5e6).abs() < 1e-6);
+        assert!((nest.plate_utilisation(&nest.plates[0]) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn mixed_blanks_do_not_overlap() {
+        let blanks = vec![blank("A", 800.0, 300.0, 7), blank("B", 200.0, 450.0, 9), blank("C", 350.0, 350.0, 5)];
+        let nest = nest(blanks, PLATE, 10.0).unwrap();
+        let placed: usize = nest.plates.iter().map(|p| p.placements.len()).sum();
+        assert_eq!(placed, 21);
+        assert_valid_layout(&nest);
+    }
+
+    #[test]
+    fn only_the_same_stock_shares_a_plate() {
+        let thick = Blank { thickness_mm: 3.0, ..blank("Thick", 100.0, 100.0, 1) };
+        let aluminium = Blank { material_name: MaterialName::Aluminum, ..blank("Alu", 100.0, 100.0, 1) };
+        let nest = nest(vec![blank("Thin", 100.0, 100.0, 2), thick, aluminium], PLATE, 10.0).unwrap();
+        assert_eq!(nest.plates.len(), 3);
+        for plate in &nest.plates {
+            for placement in &plate.placements {
+                let blank = &nest.blanks[placement.blank];
+                assert_eq!(blank.material_name, plate.material_name);
+                assert_eq!(blank.thickness_mm, plate.thickness_mm);
+            }
+        }
+    }
+
+    #[test]
+    fn oversized_blank_is_refused() {
+        assert_eq!(
+            nest(vec![blank("A", 100.0, 100.0, 1), blank("Long", 2500.0, 100.0, 1)], PLATE, 10.0),
+            Err(NestError::BlankTooLarge { job_name: "Long".to_string(), plate: PLATE })
+        );
+        // Fits without spacing, not with the edge trim on both sides
+        assert!(matches!(nest(vec![blank("Tight", 1990.0, 100.0, 1)], PLATE, 10.0), Err(NestError::BlankTooLarge { .. })));
+    }
+
+    #[test]
+    fn known_rolling_direction_fixes_the_orientation() {
+        // Narrow blanks that would rather lie rotated
+        let along_length = Blank { rolling_direction: RollingDirection::AlongLength, ..blank("L", 300.0, 800.0, 6) };
+        let along_width = Blank { rolling_direction: RollingDirection::AlongWidth, ..blank("W", 800.0, 300.0, 6) };
+        let nest = nest(vec![along_length, along_width], PLATE, 10.0).unwrap();
+        for placement in nest.plates.iter().flat_map(|p| &p.placements) {
+            match nest.blanks[placement.blank].rolling_direction {
+                RollingDirection::AlongLength => assert!(!placement.rotated, "{:?}", placement),
+                RollingDirection::AlongWidth => assert!(placement.rotated, "{:?}", placement),
+                RollingDirection::Unknown => unreachable!(),
+            }
+        }
+        assert_valid_layout(&nest);
+    }
+
+    #[test]
+    fn rolling_direction_can_make_a_blank_too_large() {
+        let long = blank("Long", 1900.0, 500.0, 1);
+        assert!(nest(vec![long.clone()], PLATE, 10.0).is_ok());
+        let along_width = Blank { rolling_direction: RollingDirection::AlongWidth, ..long };
+        assert_eq!(
+            nest(vec![along_width], PLATE, 10.0),
+            Err(NestError::BlankTooLarge { job_name: "Long".to_string(), plate: PLATE })
+        );
+    }
+
+    #[test]
+    fn nothing_to_nest() {
+        assert_eq!(nest(Vec::new(), PLATE, 10.0), Err(NestError::NoBlanks));
+        assert_eq!(nest(vec![blank("A", 100.0, 100.0, 0)], PLATE, 10.0), Err(NestError::NoBlanks));
+    }
+}
@@ -0,0 +1,227 @@
+// src/numeric_input.rs
+// Numeric input fields: the text the operator typed plus the range it must fall
+// in. The text may be an expression ("150-2*1.5") and may use a decimal comma.
+use vouwbank::units::LengthUnit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantity {
+    Length, // Typed in the display unit, stored in mm
+    Angle,  // Degrees in every unit system
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumericInputError {
+    Empty,
+    InvalidExpression { position: usize }, // Character offset of the first unexpected input
+    DivisionByZero,
+    OutOfRange { min: f64, max: f64 },   // In mm or degrees
+}
+
+#[derive(Debug, Clone)]
+pub struct NumericInput {
+    pub text: String,
+    pub quantity: Quantity,
+    pub min: f64, // Inclusive, in mm or degrees
+    pub max: f64,
+}
+
+impl NumericInput {
+    pub fn length(min_mm: f64, max_mm: f64) -> Self {
+        NumericInput { text: String::new(), quantity: Quantity::Length, min: min_mm, max: max_mm }
+    }
+
+    pub fn angle(min_deg: f64, max_deg: f64) -> Self {
+        NumericInput { text: String::new(), quantity: Quantity::Angle, min: min_deg, max: max_deg }
+    }
+
+    fn to_base(&self, value: f64, unit: LengthUnit) -> f64 {
+        match self.quantity {
+            Quantity::Length => unit.to_mm(value),
+            Quantity::Angle => value,
+        }
+    }
+
+    /// The evaluated text in mm or degrees, without the range check.
+    pub fn evaluate(&self, unit: LengthUnit) -> Result<f64, NumericInputError> {
+        if self.text.trim().is_empty() {
+            return Err(NumericInputError::Empty);
+        }
+        evaluate_expression(&self.text).map(|value| self.to_base(value, unit))
+    }
+
+    /// The evaluated text in mm or degrees, checked against the range.
+    pub fn value(&self, unit: LengthUnit) -> Result<f64, NumericInputError> {
+        let value = self.evaluate(unit)?;
+        if value < self.min || value > self.max {
+            return Err(NumericInputError::OutOfRange { min: self.min, max: self.max });
+        }
+        Ok(value)
+    }
+
+    /// Replaces the text with `value` (mm or degrees) written in `unit`.
+    pub fn set_value(&mut self, value: f64, unit: LengthUnit) {
+        self.text = match self.quantity {
+            Quantity::Length => unit.input_text(value),
+            Quantity::Angle => value.to_string(),
+        };
+    }
+
+    /// More than a plain number, so the evaluated value is worth showing.
+    pub fn is_expression(&self) -> bool {
+        self.text.trim().replace(',', ".").parse::<f64>().is_err()
+    }
+}
+
+// --- Expressions ---
+
+/// Evaluates `+ - * /`, parentheses and unary minus. A comma is a decimal
+/// separator, as typed on a Dutch keyboard layout.
+pub fn evaluate_expression(text: &str) -> Result<f64, NumericInputError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut parser = ExpressionParser { chars: &chars, pos: 0 };
+    let value = parser.sum()?;
+    parser.skip_whitespace();
+    if parser.pos < chars.len() {
+        return Err(NumericInputError::InvalidExpression { position: parser.pos });
+    }
+    Ok(value)
+}
+
+struct ExpressionParser<'a> {
+    chars: &'a [char],
+    pos: usize,
+}
+
+impl ExpressionParser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        if self.chars.get(self.pos) == Some(&expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn sum(&mut self) -> Result<f64, NumericInputError> {
+        let mut value = self.product()?;
+        loop {
+            if self.eat('+') {
+                value += self.product()?;
+            } else if self.eat('-') {
+                value -= self.product()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<f64, NumericInputError> {
+        let mut value = self.factor()?;
+        loop {
+            if self.eat('*') {
+                value *= self.factor()?;
+            } else if self.eat('/') {
+                let divisor = self.factor()?;
+                if divisor == 0.0 {
+                    return Err(NumericInputError::DivisionByZero);
+                }
+                value /= divisor;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn factor(&mut self) -> Result<f64, NumericInputError> {
+        if self.eat('-') {
+            return Ok(-self.factor()?);
+        }
+        if self.eat('(') {
+            let value = self.sum()?;
+            if !self.eat(')') {
+                return Err(NumericInputError::InvalidExpression { position: self.pos });
+            }
+            return Ok(value);
+        }
+        self.number()
+    }
+
+    fn number(&mut self) -> Result<f64, NumericInputError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.chars.get(self.pos).is_some_and(|c| c.is_ascii_digit() || *c == '.' || *c == ',') {
+            self.pos += 1;
+        }
+        let digits: String = self.chars[start..self.pos].iter().map(|c| if *c == ',' { '.' } else { *c }).collect();
+        digits.parse().map_err(|_| NumericInputError::InvalidExpression { position: start })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(result: Result<f64, NumericInputError>, expected: f64) {
+        let value = result.unwrap();
+        assert!((value - expected).abs() < 1e-9, "{} != {}", value, expected);
+    }
+
+    #[test]
+    fn evaluates_expressions() {
+        close(evaluate_expression("150-2*1.5"), 147.0);
+        close(evaluate_expression("1,5"), 1.5);
+        close(evaluate_expression("-(2+3)*2"), -10.0);
+        close(evaluate_expression(" 10 / 4 "), 2.5);
+        close(evaluate_expression("2*(3+4)-1"), 13.0);
+    }
+
+    #[test]
+    fn reports_errors_with_position() {
+        assert_eq!(evaluate_expression("1/0"), Err(NumericInputError::DivisionByZero));
+        assert_eq!(evaluate_expression("2+"), Err(NumericInputError::InvalidExpression { position: 2 }));
+        assert_eq!(evaluate_expression("(2"), Err(NumericInputError::InvalidExpression { position: 2 }));
+        assert_eq!(evaluate_expression("12x"), Err(NumericInputError::InvalidExpression { position: 2 }));
+    }
+
+    #[test]
+    fn empty_text_is_empty() {
+        assert_eq!(NumericInput::length(0.0, 100.0).value(LengthUnit::Millimeter), Err(NumericInputError::Empty));
+        let blank = NumericInput { text: "   ".to_string(), ..NumericInput::angle(0.0, 180.0) };
+        assert_eq!(blank.value(LengthUnit::Millimeter), Err(NumericInputError::Empty));
+    }
+
+    #[test]
+    fn value_checks_both_bounds() {
+        let mut input = NumericInput::length(10.0, 100.0);
+        let out_of_range = Err(NumericInputError::OutOfRange { min: 10.0, max: 100.0 });
+        for (text, expected) in [("9.99", out_of_range.clone()), ("10", Ok(10.0)), ("100", Ok(100.0)), ("100.01", out_of_range)] {
+            input.text = text.to_string();
+            assert_eq!(input.value(LengthUnit::Millimeter), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn lengths_in_inch_are_stored_in_mm() {
+        let mut input = NumericInput::length(0.0, 1000.0);
+        input.text = "1".to_string();
+        close(input.value(LengthUnit::Inch), 25.4);
+        // Angles are degrees whatever the length unit
+        let angle = NumericInput { text: "90".to_string(), ..NumericInput::angle(0.0, 180.0) };
+        close(angle.value(LengthUnit::Inch), 90.0);
+    }
+
+    #[test]
+    fn set_value_round_trips() {
+        let mut input = NumericInput::length(0.0, 1000.0);
+        input.set_value(50.8, LengthUnit::Inch);
+        close(input.value(LengthUnit::Inch), 50.8);
+        assert!(!input.is_expression());
+    }
+}
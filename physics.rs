@@ -0,0 +1,78 @@
+// src/physics.rs
+use crate::model::{BendMethod, Die, MaterialDetails, MaterialName, Punch, SheetMetal};
+
+// --- Press Brake Physics (air bending rules of thumb) ---
+pub const IDEAL_V_TO_THICKNESS: f64 = 8.0;
+pub const MIN_V_TO_THICKNESS: f64 = 6.0;
+pub const MAX_V_TO_THICKNESS: f64 = 12.0;
+pub const PRESS_CAPACITY_KN: f64 = 1000.0; // Nominal press force of the machine
+const TENSILE_TO_YIELD_RATIO: f64 = 1.5; // We only store yield stress; estimate Rm from it
+const AIR_BEND_FORCE_FACTOR: f64 = 1.33;
+pub const BOTTOMING_ANGLE_TOLERANCE_DEG: f64 = 2.0; // Tool angle must match the target this closely
+pub const HEM_PREBEND_MAX_ANGLE_DEG: f64 = 45.0; // Pre-bend must be at least this acute before flattening
+pub const HEM_FLATTEN_MAX_ANGLE_DEG: f64 = 10.0;
+const MAX_SINGLE_HIT_RADIUS_FACTOR: f64 = 1.5; // Beyond this multiple of the natural radius, bump bend
+
+/// Inner radius an air bend settles at, as a fraction of the V opening.
+/// Harder materials spring back into a wider radius.
+pub fn air_bend_radius_factor(material: &MaterialDetails) -> f64 {
+    match material.name {
+        MaterialName::Aluminum | MaterialName::Copper => 0.13,
+        MaterialName::StainlessSteel => 0.21,
+        _ => 0.16,
+    }
+}
+
+/// Natural inner radius produced by air bending in the given die.
+pub fn air_bend_inner_radius_mm(die: &Die, material: &MaterialDetails) -> f64 {
+    die.v_opening_mm * air_bend_radius_factor(material)
+}
+
+/// Largest inner radius a single air bend hit can reasonably hold in this die;
+/// anything larger needs bump bending.
+pub fn max_single_hit_radius_mm(punch: &Punch, die: &Die, material: &MaterialDetails) -> f64 {
+    (air_bend_inner_radius_mm(die, material) * MAX_SINGLE_HIT_RADIUS_FACTOR).max(punch.radius_mm)
+}
+
+/// Weight of the flat blank; bending doesn't change it.
+pub fn sheet_weight_kg(sheet: &SheetMetal, material: &MaterialDetails) -> f64 {
+    let volume_m3 = sheet.original_length_mm * sheet.width_mm * sheet.thickness_mm * 1e-9;
+    volume_m3 * material.density_kg_m3
+}
+
+/// The V opening the 8·t rule asks for.
+pub fn ideal_v_opening_mm(thickness_mm: f64) -> f64 {
+    thickness_mm * IDEAL_V_TO_THICKNESS
+}
+
+/// Air bending force over the full sheet width in kN (F = 1.33·Rm·L·t²/V).
+pub fn air_bend_force_kn(sheet: &SheetMetal, material: &MaterialDetails, die: &Die) -> f64 {
+    if die.v_opening_mm <= 0.0 {
+        return f64::INFINITY;
+    }
+    let tensile_mpa = material.yield_stress_mpa * TENSILE_TO_YIELD_RATIO;
+    AIR_BEND_FORCE_FACTOR * tensile_mpa * sheet.width_mm * sheet.thickness_mm.powi(2) / die.v_opening_mm / 1000.0
+}
+
+/// Force multiplier over air bending for each bending method.
+pub fn method_force_factor(method: BendMethod) -> f64 {
+    match method {
+        BendMethod::AirBending => 1.0,
+        BendMethod::Bottoming => 4.0,
+        BendMethod::Coining => 8.0,
+        BendMethod::Hemming => 6.0,
+    }
+}
+
+pub fn bend_force_kn(sheet: &SheetMetal, material: &MaterialDetails, die: &Die, method: BendMethod) -> f64 {
+    air_bend_force_kn(sheet, material, die) * method_force_factor(method)
+}
+
+/// Inner radius the method produces: air bending follows the V opening, the
+/// other methods press the sheet onto the punch tip.
+pub fn formed_inner_radius_mm(method: BendMethod, punch: &Punch, die: &Die, material: &MaterialDetails) -> f64 {
+    match method {
+        BendMethod::AirBending => air_bend_inner_radius_mm(die, material).max(punch.radius_mm),
+        BendMethod::Bottoming | BendMethod::Coining | BendMethod::Hemming => punch.radius_mm,
+    }
+}
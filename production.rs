@@ -0,0 +1,266 @@
+// src/production.rs
+// Production queue: orders to bend a job a number of times, the parts bent
+// against them and the totals per shift.
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+// --- Shifts ---
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Shift {
+    Early, // 06:00-14:00
+    Late,  // 14:00-22:00
+    Night, // 22:00-06:00
+}
+
+impl Shift {
+    pub fn all() -> Vec<Self> {
+        vec![Shift::Early, Shift::Late, Shift::Night]
+    }
+
+    /// The shift running at `time` and the date it started on; the night shift
+    /// counts for the evening it began.
+    pub fn at(time: NaiveDateTime) -> (Shift, NaiveDate) {
+        match time.hour() {
+            6..=13 => (Shift::Early, time.date()),
+            14..=21 => (Shift::Late, time.date()),
+            22..=23 => (Shift::Night, time.date()),
+            _ => (Shift::Night, time.date() - Duration::days(1)),
+        }
+    }
+}
+
+impl fmt::Display for Shift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shift::Early => write!(f, "Early"),
+            Shift::Late => write!(f, "Late"),
+            Shift::Night => write!(f, "Night"),
+        }
+    }
+}
+
+// --- Orders ---
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductionOrder {
+    pub id: u64,
+    pub customer: String,
+    pub job_name: String,
+    pub job_file: Option<PathBuf>, // To open the job from the queue; None for a job never saved
+    pub quantity: u32,
+    pub due_date: NaiveDate,
+    pub created: DateTime<Local>,
+}
+
+/// Parts bent for an order, as marked by the operator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BentRecord {
+    pub order_id: u64,
+    pub time: DateTime<Local>,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ProductionError {
+    #[error("Quantity must be at least 1")]
+    ZeroQuantity,
+    #[error("Order {0} is not in the queue")]
+    UnknownOrder(u64),
+    #[error("Order {order_id} has only {remaining} parts left to bend")]
+    ExceedsQuantity { order_id: u64, remaining: u32 },
+}
+
+/// The orders and every part bent against them. Records stay when an order is
+/// removed, so shift totals keep counting them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProductionQueue {
+    pub orders: Vec<ProductionOrder>, // In queue order
+    pub history: Vec<BentRecord>, // Oldest first
+    next_id: u64,
+}
+
+impl ProductionQueue {
+    pub fn add_order(
+        &mut self,
+        customer: &str,
+        job_name: &str,
+        job_file: Option<PathBuf>,
+        quantity: u32,
+        due_date: NaiveDate,
+        now: DateTime<Local>,
+    ) -> Result<u64, ProductionError> {
+        if quantity == 0 {
+            return Err(ProductionError::ZeroQuantity);
+        }
+        self.next_id += 1;
+        self.orders.push(ProductionOrder {
+            id: self.next_id,
+            customer: customer.trim().to_string(),
+            job_name: job_name.to_string(),
+            job_file,
+            quantity,
+            due_date,
+            created: now,
+        });
+        Ok(self.next_id)
+    }
+
+    pub fn remove_order(&mut self, order_id: u64) -> Option<ProductionOrder> {
+        let index = self.orders.iter().position(|o| o.id == order_id)?;
+        Some(self.orders.remove(index))
+    }
+
+    pub fn order(&self, order_id: u64) -> Option<&ProductionOrder> {
+        self.orders.iter().find(|o| o.id == order_id)
+    }
+
+    pub fn parts_bent(&self, order_id: u64) -> u32 {
+        self.history.iter().filter(|r| r.order_id == order_id).map(|r| r.count).sum()
+    }
+
+    pub fn remaining(&self, order: &ProductionOrder) -> u32 {
+        order.quantity.saturating_sub(self.parts_bent(order.id))
+    }
+
+    pub fn is_complete(&self, order: &ProductionOrder) -> bool {
+        self.remaining(order) == 0
+    }
+
+    pub fn is_overdue(&self, order: &ProductionOrder, today: NaiveDate) -> bool {
+        order.due_date < today && !self.is_complete(order)
+    }
+
+    /// Marks `count` more parts of the order as bent.
+    pub fn record_bent(&mut self, order_id: u64, count: u32, now: DateTime<Local>) -> Result<(), ProductionError> {
+        if count == 0 {
+            return Err(ProductionError::ZeroQuantity);
+        }
+        let order = self.order(order_id).ok_or(ProductionError::UnknownOrder(order_id))?;
+        let remaining = self.remaining(order);
+        if count > remaining {
+            return Err(ProductionError::ExceedsQuantity { order_id, remaining });
+        }
+        self.history.push(BentRecord { order_id, time: now, count });
+        Ok(())
+    }
+
+    /// Parts bent in one shift, over all orders.
+    pub fn shift_total(&self, shift: Shift, date: NaiveDate) -> u32 {
+        self.history.iter()
+            .filter(|r| Shift::at(r.time.naive_local()) == (shift, date))
+            .map(|r| r.count)
+            .sum()
+    }
+
+    /// Parts bent per shift that has any, most recent first.
+    pub fn shift_totals(&self) -> Vec<(NaiveDate, Shift, u32)> {
+        let mut totals: Vec<(NaiveDate, Shift, u32)> = Vec::new();
+        for record in &self.history {
+            let (shift, date) = Shift::at(record.time.naive_local());
+            match totals.iter_mut().find(|(d, s, _)| *d == date && *s == shift) {
+                Some((_, _, total)) => *total += record.count,
+                None => totals.push((date, shift, record.count)),
+            }
+        }
+        totals.sort_by_key(|(date, shift, _)| std::cmp::Reverse((*date, *shift)));
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local.from_local_datetime(&date(day).and_hms_opt(hour, minute, 0).unwrap()).earliest().unwrap()
+    }
+
+    fn queue_with_order(quantity: u32) -> (ProductionQueue, u64) {
+        let mut queue = ProductionQueue::default();
+        let id = queue.add_order(" Klant ", "Bracket", None, quantity, date(20), at(9, 8, 0)).unwrap();
+        (queue, id)
+    }
+
+    #[test]
+    fn shift_boundaries() {
+        let naive = |hour, minute| date(10).and_hms_opt(hour, minute, 0).unwrap();
+        assert_eq!(Shift::at(naive(5, 59)), (Shift::Night, date(9)));
+        assert_eq!(Shift::at(naive(6, 0)), (Shift::Early, date(10)));
+        assert_eq!(Shift::at(naive(13, 59)), (Shift::Early, date(10)));
+        assert_eq!(Shift::at(naive(14, 0)), (Shift::Late, date(10)));
+        assert_eq!(Shift::at(naive(21, 59)), (Shift::Late, date(10)));
+        assert_eq!(Shift::at(naive(22, 0)), (Shift::Night, date(10)));
+        assert_eq!(Shift::at(naive(0, 0)), (Shift::Night, date(9)));
+    }
+
+    #[test]
+    fn recording_checks_the_quantity() {
+        let (mut queue, id) = queue_with_order(10);
+        assert_eq!(queue.order(id).unwrap().customer, "Klant");
+        assert_eq!(queue.record_bent(id, 0, at(10, 8, 0)), Err(ProductionError::ZeroQuantity));
+        queue.record_bent(id, 7, at(10, 8, 0)).unwrap();
+        assert_eq!(queue.record_bent(id, 4, at(10, 9, 0)), Err(ProductionError::ExceedsQuantity { order_id: id, remaining: 3 }));
+        assert_eq!(queue.record_bent(id + 1, 1, at(10, 9, 0)), Err(ProductionError::UnknownOrder(id + 1)));
+        queue.record_bent(id, 3, at(10, 9, 0)).unwrap();
+
+        let order = queue.order(id).unwrap().clone();
+        assert!(queue.is_complete(&order));
+        assert!(!queue.is_overdue(&order, date(25)));
+    }
+
+    #[test]
+    fn zero_quantity_order_is_rejected() {
+        let mut queue = ProductionQueue::default();
+        assert_eq!(queue.add_order("", "Bracket", None, 0, date(20), at(9, 8, 0)), Err(ProductionError::ZeroQuantity));
+        assert!(queue.orders.is_empty());
+    }
+
+    #[test]
+    fn overdue_until_complete() {
+        let (mut queue, id) = queue_with_order(5);
+        queue.record_bent(id, 2, at(10, 8, 0)).unwrap();
+        let order = queue.order(id).unwrap().clone();
+        assert_eq!(queue.remaining(&order), 3);
+        assert!(!queue.is_overdue(&order, date(20)));
+        assert!(queue.is_overdue(&order, date(21)));
+    }
+
+    #[test]
+    fn shift_totals_most_recent_first() {
+        let (mut queue, id) = queue_with_order(100);
+        queue.record_bent(id, 1, at(10, 7, 0)).unwrap();  // Early on the 10th
+        queue.record_bent(id, 2, at(10, 15, 0)).unwrap(); // Late on the 10th
+        queue.record_bent(id, 3, at(10, 23, 0)).unwrap(); // Night of the 10th
+        queue.record_bent(id, 4, at(11, 5, 0)).unwrap();  // Still the night of the 10th
+        queue.record_bent(id, 5, at(11, 6, 0)).unwrap();  // Early on the 11th
+        assert_eq!(queue.shift_totals(), vec![
+            (date(11), Shift::Early, 5),
+            (date(10), Shift::Night, 7),
+            (date(10), Shift::Late, 2),
+            (date(10), Shift::Early, 1),
+        ]);
+        assert_eq!(queue.shift_total(Shift::Night, date(10)), 7);
+        assert_eq!(queue.shift_total(Shift::Night, date(11)), 0);
+    }
+
+    #[test]
+    fn records_outlive_removed_orders() {
+        let (mut queue, id) = queue_with_order(10);
+        queue.record_bent(id, 4, at(10, 8, 0)).unwrap();
+        assert_eq!(queue.remove_order(id).map(|o| o.id), Some(id));
+        assert_eq!(queue.remove_order(id), None);
+        assert_eq!(queue.parts_bent(id), 4);
+        assert_eq!(queue.shift_total(Shift::Early, date(10)), 4);
+        // Ids are not reused
+        let next = queue.add_order("", "Bracket", None, 1, date(20), at(10, 9, 0)).unwrap();
+        assert_ne!(next, id);
+    }
+}
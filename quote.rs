@@ -0,0 +1,149 @@
+// src/quote.rs
+// Part and batch cost for quotes: blank material by weight, machine time from
+// the cycle time estimate and the setup spread over the batch.
+use crate::model::{Job, MachineSetup};
+use crate::physics;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteRates {
+    pub machine_rate_per_hour: f64, // Press brake with operator
+    pub setup_minutes: f64,         // Tooling and first-part check, once per batch
+    pub currency: String,
+}
+
+impl Default for QuoteRates {
+    fn default() -> Self {
+        QuoteRates { machine_rate_per_hour: 75.0, setup_minutes: 20.0, currency: "EUR".to_string() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quote {
+    pub job_name: String,
+    pub material: String,
+    pub quantity: u32,
+    pub weight_kg: f64,        // Per part
+    pub price_per_kg: f64,
+    pub cycle_time_s: f64,     // Per part
+    pub material_cost: f64,    // Per part
+    pub machine_cost: f64,     // Per part
+    pub setup_cost: f64,       // Per batch
+    pub currency: String,
+}
+
+impl Quote {
+    /// Material and machine time of one part, without setup.
+    pub fn part_cost(&self) -> f64 {
+        self.material_cost + self.machine_cost
+    }
+
+    /// Part cost plus its share of the setup.
+    pub fn unit_price(&self) -> f64 {
+        self.batch_total() / self.quantity.max(1) as f64
+    }
+
+    pub fn batch_total(&self) -> f64 {
+        self.part_cost() * self.quantity as f64 + self.setup_cost
+    }
+}
+
+/// Costs `quantity` parts of the job. The material price comes from the
+/// material library, the cycle time from `cycle_time::estimate`.
+pub fn quote(setup: &MachineSetup, job: &Job, cycle_time_s: f64, rates: &QuoteRates, quantity: u32) -> Quote {
+    let weight_kg = physics::sheet_weight_kg(setup.sheet, setup.material);
+    let rate_per_s = rates.machine_rate_per_hour / 3600.0;
+    Quote {
+        job_name: job.name.clone(),
+        material: setup.material.name.to_string(),
+        quantity,
+        weight_kg,
+        price_per_kg: setup.material.price_per_kg,
+        cycle_time_s,
+        material_cost: weight_kg * setup.material.price_per_kg,
+        machine_cost: cycle_time_s * rate_per_s,
+        setup_cost: rates.setup_minutes * 60.0 * rate_per_s,
+        currency: rates.currency.clone(),
+    }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// One header row and one quote row, for spreadsheets.
+pub fn to_csv(quote: &Quote) -> String {
+    let mut csv = String::new();
+    let _ = writeln!(
+        csv,
+        "job,material,quantity,weight_kg,price_per_kg,material_cost,cycle_time_s,machine_cost,setup_cost,unit_price,batch_total,currency"
+    );
+    let _ = writeln!(
+        csv,
+        "{},{},{},{:.3},{:.2},{:.2},{:.1},{:.2},{:.2},{:.2},{:.2},{}",
+        csv_field(&quote.job_name), csv_field(&quote.material), quote.quantity, quote.weight_kg, quote.price_per_kg,
+        quote.material_cost, quote.cycle_time_s, quote.machine_cost, quote.setup_cost, quote.unit_price(), quote.batch_total(),
+        csv_field(&quote.currency)
+    );
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{self, MaterialName, SheetMetal};
+
+    fn close(value: f64, expected: f64) {
+        assert!((value - expected).abs() < 1e-9, "{} != {}", value, expected);
+    }
+
+    fn steel_quote(quantity: u32) -> Quote {
+        let materials = model::default_materials();
+        let (punches, dies) = (model::default_punches(), model::default_dies());
+        let job = Job {
+            name: "Bracket".to_string(),
+            sheet: SheetMetal { original_length_mm: 1000.0, width_mm: 1000.0, thickness_mm: 1.0, ..SheetMetal::default() },
+            ..Job::default()
+        };
+        let setup = MachineSetup { sheet: &job.sheet, material: &materials[&MaterialName::Steel], punch: &punches[0], die: &dies[0] };
+        let rates = QuoteRates { machine_rate_per_hour: 72.0, setup_minutes: 20.0, currency: "EUR".to_string() };
+        quote(&setup, &job, 30.0, &rates, quantity)
+    }
+
+    #[test]
+    fn costs_add_up() {
+        let quote = steel_quote(10);
+        close(quote.weight_kg, 7.85);
+        close(quote.material_cost, 7.85 * 1.10);
+        close(quote.machine_cost, 30.0 * 0.02); // 72 per hour is 0.02 per second
+        close(quote.setup_cost, 20.0 * 60.0 * 0.02);
+        close(quote.batch_total(), quote.part_cost() * 10.0 + 24.0);
+        // Each part carries a tenth of the setup
+        close(quote.unit_price(), quote.part_cost() + 2.4);
+    }
+
+    #[test]
+    fn empty_batch_costs_the_setup() {
+        let quote = steel_quote(0);
+        close(quote.batch_total(), 24.0);
+        close(quote.unit_price(), 24.0);
+    }
+
+    #[test]
+    fn csv_quotes_separators_in_fields() {
+        assert_eq!(csv_field("Bracket"), "Bracket");
+        assert_eq!(csv_field("Bracket, left"), "\"Bracket, left\"");
+        assert_eq!(csv_field("12\" panel"), "\"12\"\" panel\"");
+
+        let quote = Quote { job_name: "Bracket, \"left\"".to_string(), ..steel_quote(10) };
+        let csv = to_csv(&quote);
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.starts_with("\"Bracket, \"\"left\"\"\",Steel,10,"), "{}", row);
+        assert_eq!(csv.lines().next().unwrap().split(',').count(), 12);
+    }
+}
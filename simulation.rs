@@ -0,0 +1,19 @@
+// src/simulation.rs
+use crate::geometry;
+use crate::model::{BendStep, Job, MachineSetup};
+use crate::validation::{self, ValidationError, ValidationWarning};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationResult {
+    pub hits: Vec<BendStep>,        // Machine hits in execution order
+    pub profile: Vec<[f64; 2]>,     // Centre line of the finished part, see geometry::profile_polyline
+    pub warnings: Vec<ValidationWarning>,
+}
+
+/// Validates the job against the setup and computes the finished profile.
+pub fn simulate(setup: &MachineSetup, job: &Job) -> Result<SimulationResult, ValidationError> {
+    let warnings = validation::validate_job(setup, job)?;
+    let hits = geometry::expand_machine_steps(job);
+    let profile = geometry::profile_polyline(job, &hits);
+    Ok(SimulationResult { hits, profile, warnings })
+}
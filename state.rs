@@ -1,186 +1,243 @@
 // src/state.rs
 
-use egui::{TextureHandle, Vec2, Color32}; // Added Color32
-use std::collections::HashMap; // For materials map
-
-// --- CNC Specific Enums and Structs ---
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)] // Added Hash for HashMap key
-pub enum MaterialName {
-    Steel,
-    Aluminum,
-    StainlessSteel,
-    Copper,
-    MildSteel,
-    Custom(String), // Allow custom material names
-}
-
-impl MaterialName {
-    pub fn default_names() -> Vec<Self> {
-        vec![
-            MaterialName::Steel,
-            MaterialName::Aluminum,
-            MaterialName::StainlessSteel,
-            MaterialName::Copper,
-            MaterialName::MildSteel,
-        ]
+use crate::i18n::{self, Language};
+use crate::message_log::{LogLevel, MessageLog};
+use crate::numeric_input::NumericInput;
+use egui::{TextureHandle, Vec2};
+use std::collections::{BTreeSet, HashMap}; // HashMap for the materials map
+use std::path::PathBuf;
+use vouwbank::cycle_time::MachineSpeeds;
+use vouwbank::model::{self, BendStep, Die, Job, MaterialDetails, MaterialName, Punch};
+use vouwbank::nesting::{self, Blank};
+use vouwbank::production::ProductionQueue;
+use vouwbank::quote::QuoteRates;
+use vouwbank::simulation::SimulationResult;
+use vouwbank::tooling::ToolingRecommendation;
+use vouwbank::units::LengthUnit;
+use vouwbank::validation;
+
+// --- UI Input State ---
+/// Length fields hold text in the display unit (`AppState::length_unit`), not mm.
+#[derive(Clone)]
+pub struct SheetInputState {
+    pub length: NumericInput,
+    pub thickness: NumericInput, // Also accepts a US gauge ("16ga")
+    pub width: NumericInput,
+    pub selected_material_idx: usize,
+}
+
+impl Default for SheetInputState {
+    fn default() -> Self {
+        let dimension = || NumericInput::length(validation::MIN_SHEET_DIMENSION_MM, validation::MAX_SHEET_DIMENSION_MM);
+        SheetInputState { length: dimension(), thickness: dimension(), width: dimension(), selected_material_idx: 0 }
     }
+}
+
+#[derive(Clone)]
+pub struct BendInputState {
+    pub position: NumericInput, // Range follows the sheet length
+    pub target_angle: NumericInput,
+    pub radius: NumericInput,
+    pub selected_direction_idx: usize,
+    pub selected_method_idx: usize,
+    pub selected_feature_idx: usize, // Into BendFeatureKind::all()
+    pub joggle_offset: NumericInput,
+}
 
-    pub fn to_string(&self) -> String {
-        match self {
-            MaterialName::Steel => "Steel".to_string(),
-            MaterialName::Aluminum => "Aluminum".to_string(),
-            MaterialName::StainlessSteel => "Stainless Steel".to_string(),
-            MaterialName::Copper => "Copper".to_string(),
-            MaterialName::MildSteel => "Mild Steel".to_string(),
-            MaterialName::Custom(name) => name.clone(),
+impl Default for BendInputState {
+    fn default() -> Self {
+        BendInputState {
+            position: NumericInput::length(0.0, validation::MAX_SHEET_DIMENSION_MM),
+            target_angle: NumericInput::angle(validation::MIN_BEND_ANGLE_DEG, validation::MAX_BEND_ANGLE_DEG),
+            radius: NumericInput::length(validation::MIN_BEND_RADIUS_MM, validation::MAX_BEND_RADIUS_MM),
+            selected_direction_idx: 0,
+            selected_method_idx: 0,
+            selected_feature_idx: 0,
+            joggle_offset: NumericInput::length(validation::MIN_SHEET_DIMENSION_MM, validation::MAX_SHEET_DIMENSION_MM),
         }
     }
-    // You might want a FromStr implementation too for parsing user input
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct MaterialDetails {
-    pub name: MaterialName,
-    pub density_kg_m3: f64,    // kg/m^3
-    pub yield_stress_mpa: f64, // MPa
-    pub tensile_modulus_gpa: f64, // GPa (Young's Modulus)
-    pub min_bend_radius_factor: f64, // Factor times thickness
+#[derive(Default, Clone)]
+pub struct ToolingInputState {
+    pub selected_punch_idx: usize,
+    pub selected_die_idx: usize,
 }
 
+
+pub const MAX_RECENT_FILES: usize = 8;
+pub const MAX_UNDO_STEPS: usize = 50; // Per open job
+pub const DATE_FORMAT: &str = "%Y-%m-%d"; // Due dates as typed and shown
+pub const DEFAULT_WINDOW_SIZE: [f32; 2] = [800.0, 600.0]; // Until eframe restores the saved geometry
+
+/// Job actions; closing a job or the app waits for the operator's answer when
+/// that would drop unsaved changes.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum BendDirection {
-    Up,
-    Down,
+pub enum PendingJobAction {
+    NewJob,
+    OpenJob, // Ask for a file first
+    OpenFile(PathBuf),
+    CloseJob, // The active tab
+    Exit,
 }
 
-impl BendDirection {
-    pub fn default_directions() -> Vec<Self> {
-        vec![BendDirection::Up, BendDirection::Down]
-    }
-    pub fn to_string(&self) -> String {
-        match self {
-            BendDirection::Up => "Up".to_string(),
-            BendDirection::Down => "Down".to_string(),
-        }
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsavedChangesChoice {
+    Save,
+    Discard,
+    Cancel,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct BendStep {
-    pub sequence_order: usize, // 1-based
-    pub position_mm: f64,
-    pub target_angle_deg: f64,
-    pub radius_mm: f64,
-    pub direction: BendDirection,
+/// Failed load/save shown in the error dialog until dismissed.
+#[derive(Debug, Clone)]
+pub struct StorageErrorReport {
+    pub action: String, // e.g. "Save failed", already translated
+    pub path: PathBuf,
+    pub cause: String,  // Error with its source chain, one per line
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct SheetMetal {
-    pub id: String,
-    pub original_length_mm: f64,
-    pub thickness_mm: f64,
-    pub width_mm: f64,
-    pub material_name: MaterialName, // Store by name, lookup details in AppState.materials
-    // CurrentBends would be part of a "ProcessedSheet" or similar,
-    // or the simulation would directly modify a visual representation.
-    // For now, let's assume the BendSteps in the Job define the target.
+// --- Open Jobs ---
+/// One job tab: the job plus everything that belongs to it rather than to the app.
+pub struct JobTab {
+    pub job: Job,
+    pub file: Option<PathBuf>, // None until the job is saved or loaded
+    pub dirty: bool, // Unsaved changes since the last save/load
+    pub undo_stack: Vec<Job>, // Job before each edit, most recent last
+    pub redo_stack: Vec<Job>,
+    pub selected_steps: BTreeSet<usize>, // Indices into job.steps, for copy
+    pub simulation_status: &'static str, // Catalog key, e.g. "exec.ready"
+    pub simulation_result: Option<SimulationResult>, // Hits and profile of the last successful simulation
+    pub profile_load_status: &'static str, // Catalog key, shown while there is no profile
 }
 
-impl Default for SheetMetal {
-    fn default() -> Self {
-        SheetMetal {
-            id: "DefaultSheet-001".to_string(),
-            original_length_mm: 300.0,
-            thickness_mm: 2.0,
-            width_mm: 100.0,
-            material_name: MaterialName::Steel,
+impl JobTab {
+    pub fn new(job: Job, file: Option<PathBuf>) -> Self {
+        JobTab {
+            job,
+            file,
+            dirty: false,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            selected_steps: BTreeSet::new(),
+            simulation_status: "exec.ready",
+            simulation_result: None,
+            profile_load_status: "profile.not_generated",
         }
     }
-}
 
+    /// A new job nobody has touched yet, which an opened job may replace.
+    pub fn is_pristine(&self) -> bool {
+        self.file.is_none() && !self.dirty && self.undo_stack.is_empty()
+    }
+}
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Punch {
-    pub name: String,
-    pub height_mm: f64,
-    pub angle_deg: f64,
-    pub radius_mm: f64,
+// --- Job Library ---
+pub struct JobLibraryEntry {
+    pub path: PathBuf,
+    pub job: Job,
+    pub profile: Vec<[f64; 2]>, // Thumbnail, see geometry::profile_polyline
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Die {
-    pub name: String,
-    pub v_opening_mm: f64,
-    pub angle_deg: f64,
-    pub shoulder_radius_mm: f64,
+#[derive(Default)]
+pub struct JobLibraryState {
+    pub open: bool,
+    pub directory_str: String,
+    pub entries: Vec<JobLibraryEntry>,
+    pub unreadable_files: usize, // JSON files in the directory that aren't jobs
+    pub search: String,
+    pub material_filter: Option<MaterialName>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Job {
-    pub name: String,
-    pub sheet: SheetMetal, // The workpiece definition for this job
-    pub steps: Vec<BendStep>,
+// --- Production ---
+/// The production queue window and its "new order" form.
+pub struct ProductionState {
+    pub open: bool,
+    pub queue: ProductionQueue, // Persisted by the app
+    pub customer: String,
+    pub quantity: String,
+    pub due_date: String, // YYYY-MM-DD
 }
 
-impl Default for Job {
+impl Default for ProductionState {
     fn default() -> Self {
-        Job {
-            name: "DefaultJob-001".to_string(),
-            sheet: SheetMetal::default(),
-            steps: Vec::new(),
+        ProductionState {
+            open: false,
+            queue: ProductionQueue::default(),
+            customer: String::new(),
+            quantity: "1".to_string(),
+            due_date: (chrono::Local::now().date_naive() + chrono::Duration::days(7)).format(DATE_FORMAT).to_string(),
         }
     }
 }
 
-// --- UI Input State ---
-#[derive(Default, Clone)]
-pub struct SheetInputState {
-    pub length_mm_str: String,
-    pub thickness_mm_str: String,
-    pub width_mm_str: String,
-    pub selected_material_idx: usize,
+// --- Quoting ---
+pub struct QuoteState {
+    pub open: bool,
+    pub quantity: u32, // Batch size
+    pub rates: QuoteRates, // Persisted by the app, like the material prices
 }
 
-#[derive(Default, Clone)]
-pub struct BendInputState {
-    pub position_mm_str: String,
-    pub target_angle_deg_str: String,
-    pub radius_mm_str: String,
-    pub selected_direction_idx: usize,
+impl Default for QuoteState {
+    fn default() -> Self {
+        QuoteState { open: false, quantity: 100, rates: QuoteRates::default() }
+    }
 }
 
-#[derive(Default, Clone)]
-pub struct ToolingInputState {
-    pub selected_punch_idx: usize,
-    pub selected_die_idx: usize,
+// --- Nesting ---
+/// Blanks to nest and the plate they go on.
+pub struct NestState {
+    pub open: bool,
+    pub blanks: Vec<Blank>,
+    pub plate_idx: usize, // Into nesting::standard_plates()
+    pub spacing_mm: f64,
+    pub quantity: u32, // For the next job added
+    pub preview_plate: usize, // 1-based, as shown
 }
 
+impl Default for NestState {
+    fn default() -> Self {
+        NestState { open: false, blanks: Vec::new(), plate_idx: 2, spacing_mm: nesting::DEFAULT_PART_SPACING_MM, quantity: 10, preview_plate: 1 }
+    }
+}
 
 // --- Main Application State ---
 pub struct AppState {
     // Core Data
-    pub current_job: Job,
+    pub tabs: Vec<JobTab>, // Never empty
+    pub active_tab: usize,
     pub available_materials: HashMap<MaterialName, MaterialDetails>,
     pub material_display_order: Vec<MaterialName>, // For consistent UI dropdown order
 
     pub available_punches: Vec<Punch>,
     pub available_dies: Vec<Die>,
 
+    // Job Files
+    pub pending_job_action: Option<PendingJobAction>, // Shown as the "unsaved changes" prompt
+    pub close_confirmed: bool, // Let the next close request through
+    pub window_title: String, // Last title sent to the viewport
+    pub recent_files: Vec<PathBuf>, // Most recent first, persisted by the app
+    pub job_library: JobLibraryState,
+
     // UI Interaction State
     pub sheet_input: SheetInputState,
     pub bend_input: BendInputState,
     pub tooling_input: ToolingInputState,
+    pub tooling_recommendations: Vec<ToolingRecommendation>, // Ranked, best first
+    pub step_clipboard: Vec<BendStep>, // Copied bend steps, pasted into any open job
 
-    // Simulation / Output State
-    pub simulation_status: String, // e.g., "Ready", "Processing...", "Bend 1/5 complete"
-    pub parts_bent_session: u32,
-    pub simulated_profile_texture: Option<TextureHandle>, // For the SVG or rendered profile
-    pub simulated_profile_size: Option<Vec2>,
-    pub profile_load_status: String,
+    // Production
+    pub production: ProductionState,
+    pub quote: QuoteState,
+    pub nest: NestState,
+    pub machine_speeds: MachineSpeeds, // Persisted by the app; for cycle times, quotes and flange lifts
 
     // General UI state
-    pub status_message: (String, Option<Color32>), // Message and optional color (e.g. for errors)
+    pub language: Language,
+    pub length_unit: LengthUnit, // Display unit; the job itself stays in mm
+    pub message_log: MessageLog, // Status bar shows the latest entry
+    pub show_message_log: bool,
+    pub show_machine_speeds: bool,
+    pub storage_error: Option<StorageErrorReport>,
     // ... any other general state from the previous skeleton, like image_texture for a logo
     pub app_logo_texture: Option<TextureHandle>,
     pub app_logo_size: Option<Vec2>,
@@ -188,54 +245,67 @@ pub struct AppState {
 
 impl Default for AppState {
     fn default() -> Self {
-        let mut materials = HashMap::new();
-        materials.insert(MaterialName::Steel, MaterialDetails { name: MaterialName::Steel, density_kg_m3: 7850.0, yield_stress_mpa: 250.0, tensile_modulus_gpa: 200.0, min_bend_radius_factor: 1.5 });
-        materials.insert(MaterialName::Aluminum, MaterialDetails { name: MaterialName::Aluminum, density_kg_m3: 2700.0, yield_stress_mpa: 100.0, tensile_modulus_gpa: 70.0, min_bend_radius_factor: 1.0 });
-        materials.insert(MaterialName::StainlessSteel, MaterialDetails { name: MaterialName::StainlessSteel, density_kg_m3: 8000.0, yield_stress_mpa: 215.0, tensile_modulus_gpa: 193.0, min_bend_radius_factor: 2.0 });
-        materials.insert(MaterialName::Copper, MaterialDetails { name: MaterialName::Copper, density_kg_m3: 8960.0, yield_stress_mpa: 70.0, tensile_modulus_gpa: 117.0, min_bend_radius_factor: 0.8 });
-        materials.insert(MaterialName::MildSteel, MaterialDetails { name: MaterialName::MildSteel, density_kg_m3: 7850.0, yield_stress_mpa: 220.0, tensile_modulus_gpa: 200.0, min_bend_radius_factor: 1.2 });
-
+        let materials = model::default_materials();
         let material_display_order = MaterialName::default_names();
-
-        let punches = vec![
-            Punch { name: "P88.10.R06".to_string(), height_mm: 60.0, angle_deg: 88.0, radius_mm: 0.6 },
-            Punch { name: "P30.15.R1".to_string(), height_mm: 65.0, angle_deg: 30.0, radius_mm: 1.0 },
-            Punch { name: "Default Punch".to_string(), height_mm: 50.0, angle_deg: 90.0, radius_mm: 1.0 },
-        ];
-        let dies = vec![
-            Die { name: "D12.90.R2".to_string(), v_opening_mm: 12.0, angle_deg: 90.0, shoulder_radius_mm: 2.0 },
-            Die { name: "D20.60.R3".to_string(), v_opening_mm: 20.0, angle_deg: 60.0, shoulder_radius_mm: 3.0 },
-            Die { name: "Default Die".to_string(), v_opening_mm: 16.0, angle_deg: 90.0, shoulder_radius_mm: 2.0 },
-        ];
+        let punches = model::default_punches();
+        let dies = model::default_dies();
 
         let current_job = Job::default();
         let mut sheet_input = SheetInputState {
-            length_mm_str: current_job.sheet.original_length_mm.to_string(),
-            thickness_mm_str: current_job.sheet.thickness_mm.to_string(),
-            width_mm_str: current_job.sheet.width_mm.to_string(),
             selected_material_idx: material_display_order.iter().position(|n| *n == current_job.sheet.material_name).unwrap_or(0),
+            ..Default::default()
         };
+        sheet_input.length.set_value(current_job.sheet.original_length_mm, LengthUnit::default());
+        sheet_input.thickness.set_value(current_job.sheet.thickness_mm, LengthUnit::default());
+        sheet_input.width.set_value(current_job.sheet.width_mm, LengthUnit::default());
 
 
-        Self {
-            current_job,
+        let mut state = Self {
+            tabs: vec![JobTab::new(current_job, None)],
+            active_tab: 0,
             available_materials: materials,
             material_display_order,
             available_punches: punches,
             available_dies: dies,
+            pending_job_action: None,
+            close_confirmed: false,
+            window_title: String::new(),
+            recent_files: Vec::new(),
+            job_library: JobLibraryState { directory_str: "jobs".to_string(), ..Default::default() },
             sheet_input,
             bend_input: BendInputState::default(),
             tooling_input: ToolingInputState::default(),
-            simulation_status: "Ready".to_string(),
-            parts_bent_session: 0,
-            simulated_profile_texture: None,
-            simulated_profile_size: None,
-            profile_load_status: "Profile not generated.".to_string(),
-            status_message: ("System Initialized.".to_string(), None),
+            tooling_recommendations: Vec::new(),
+            step_clipboard: Vec::new(),
+            production: ProductionState::default(),
+            quote: QuoteState::default(),
+            nest: NestState::default(),
+            machine_speeds: MachineSpeeds::default(),
+            language: Language::default(),
+            length_unit: LengthUnit::default(),
+            message_log: MessageLog::default(),
+            show_message_log: false,
+            show_machine_speeds: false,
+            storage_error: None,
             app_logo_texture: None,
             app_logo_size: None,
-        }
+        };
+        state.log(LogLevel::Info, i18n::tr(state.language, "app.initialized"));
+        state
     }
 }
 
+impl AppState {
+    pub fn tab(&self) -> &JobTab {
+        &self.tabs[self.active_tab]
+    }
+
+    pub fn tab_mut(&mut self) -> &mut JobTab {
+        &mut self.tabs[self.active_tab]
+    }
 
+    /// Adds a message to the log; the status bar shows it until the next one.
+    pub fn log(&mut self, level: LogLevel, text: impl Into<String>) {
+        self.message_log.push(level, text);
+    }
+}
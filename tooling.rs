@@ -0,0 +1,240 @@
+// src/tooling.rs
+use crate::geometry;
+use crate::model::{Die, Job, MaterialDetails, Punch};
+use crate::physics;
+use std::fmt;
+
+// --- Tooling Recommendation ---
+
+/// Why a punch/die combination ranked where it did.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecommendationReason {
+    VOpeningRatio { v_opening_mm: f64, ratio: f64, within_range: bool },
+    EstimatedRadius { estimated_mm: f64, requested_mm: f64 },
+    PunchRadiusTooLarge { punch_radius_mm: f64 },
+    Force { force_kn: f64, capacity_kn: f64 },
+    FlangeTooShort { flange_mm: f64, min_flange_mm: f64 },
+    ToolAngleTooOpen { punch_angle_deg: f64, die_angle_deg: f64, target_angle_deg: f64 },
+    NoOverbendRoom { die_angle_deg: f64 },
+}
+
+impl fmt::Display for RecommendationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecommendationReason::VOpeningRatio { v_opening_mm, ratio, within_range: true } =>
+                write!(f, "V{} is {:.1}·t (ideal {:.0}·t)", v_opening_mm, ratio, physics::IDEAL_V_TO_THICKNESS),
+            RecommendationReason::VOpeningRatio { v_opening_mm, ratio, within_range: false } =>
+                write!(f, "V{} is {:.1}·t, outside the 6-12·t range", v_opening_mm, ratio),
+            RecommendationReason::EstimatedRadius { estimated_mm, requested_mm } =>
+                write!(f, "Radius ≈ {:.2}mm (requested {:.2}mm)", estimated_mm, requested_mm),
+            RecommendationReason::PunchRadiusTooLarge { punch_radius_mm } =>
+                write!(f, "Punch radius {}mm exceeds the requested radius", punch_radius_mm),
+            RecommendationReason::Force { force_kn, capacity_kn } if force_kn > capacity_kn =>
+                write!(f, "Force {:.0}kN exceeds press capacity {:.0}kN", force_kn, capacity_kn),
+            RecommendationReason::Force { force_kn, .. } =>
+                write!(f, "Force ≈ {:.0}kN", force_kn),
+            RecommendationReason::FlangeTooShort { flange_mm, min_flange_mm } =>
+                write!(f, "Shortest flange {:.1}mm < min {:.1}mm", flange_mm, min_flange_mm),
+            RecommendationReason::ToolAngleTooOpen { punch_angle_deg, die_angle_deg, target_angle_deg } =>
+                write!(f, "Tool angle (punch {}°, die {}°) too open for {}°", punch_angle_deg, die_angle_deg, target_angle_deg),
+            RecommendationReason::NoOverbendRoom { die_angle_deg } =>
+                write!(f, "Die angle {}° leaves no room for overbending", die_angle_deg),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolingRecommendation {
+    pub punch_idx: usize,
+    pub die_idx: usize,
+    pub penalty: f64, // Lower is better
+    pub usable: bool,
+    pub estimated_radius_mm: f64,
+    pub estimated_force_kn: f64,
+    pub reasons: Vec<RecommendationReason>,
+}
+
+/// Ranks every punch/die combination for the job's sheet, the requested inner
+/// radius and the target (included) angle. Unusable combinations sort last.
+pub fn recommend_tooling(
+    job: &Job,
+    material: &MaterialDetails,
+    punches: &[Punch],
+    dies: &[Die],
+    radius_mm: f64,
+    target_angle_deg: f64,
+) -> Vec<ToolingRecommendation> {
+    let sheet = &job.sheet;
+    let shortest_flange = geometry::compute_flange_lengths(job).iter()
+        .map(|f| f.shortest_mm())
+        .fold(f64::INFINITY, f64::min);
+
+    let mut recommendations = Vec::new();
+    for (punch_idx, punch) in punches.iter().enumerate() {
+        for (die_idx, die) in dies.iter().enumerate() {
+            let mut penalty = 0.0;
+            let mut usable = true;
+            let mut reasons = Vec::new();
+
+            // V opening vs thickness (6-12·t, ideally 8·t)
+            let v_ratio = die.v_opening_mm / sheet.thickness_mm;
+            let within_range = (physics::MIN_V_TO_THICKNESS..=physics::MAX_V_TO_THICKNESS).contains(&v_ratio);
+            usable &= within_range;
+            reasons.push(RecommendationReason::VOpeningRatio { v_opening_mm: die.v_opening_mm, ratio: v_ratio, within_range });
+            penalty += (v_ratio - physics::IDEAL_V_TO_THICKNESS).abs();
+
+            // Radius match: the air bend radius follows the V opening, unless the punch is blunter
+            let estimated_radius_mm = physics::air_bend_inner_radius_mm(die, material).max(punch.radius_mm);
+            let radius_error = (estimated_radius_mm - radius_mm).abs();
+            penalty += 2.0 * radius_error / sheet.thickness_mm;
+            reasons.push(RecommendationReason::EstimatedRadius { estimated_mm: estimated_radius_mm, requested_mm: radius_mm });
+            if punch.radius_mm > radius_mm + 1e-6 {
+                penalty += 2.0;
+                reasons.push(RecommendationReason::PunchRadiusTooLarge { punch_radius_mm: punch.radius_mm });
+            }
+
+            // Tonnage
+            let estimated_force_kn = physics::air_bend_force_kn(sheet, material, die);
+            usable &= estimated_force_kn <= physics::PRESS_CAPACITY_KN;
+            reasons.push(RecommendationReason::Force { force_kn: estimated_force_kn, capacity_kn: physics::PRESS_CAPACITY_KN });
+            penalty += estimated_force_kn / physics::PRESS_CAPACITY_KN;
+
+            // Minimum flange of the bends already in the job
+            let min_flange_mm = geometry::min_flange_for_die(die);
+            if shortest_flange < min_flange_mm {
+                usable = false;
+                reasons.push(RecommendationReason::FlangeTooShort { flange_mm: shortest_flange, min_flange_mm });
+            }
+
+            // Tool angles must close further than the target angle to allow for springback
+            if punch.angle_deg > target_angle_deg || die.angle_deg > target_angle_deg {
+                usable = false;
+                reasons.push(RecommendationReason::ToolAngleTooOpen {
+                    punch_angle_deg: punch.angle_deg,
+                    die_angle_deg: die.angle_deg,
+                    target_angle_deg,
+                });
+            } else if die.angle_deg >= target_angle_deg - 1e-6 {
+                penalty += 1.0;
+                reasons.push(RecommendationReason::NoOverbendRoom { die_angle_deg: die.angle_deg });
+            }
+
+            recommendations.push(ToolingRecommendation {
+                punch_idx,
+                die_idx,
+                penalty,
+                usable,
+                estimated_radius_mm,
+                estimated_force_kn,
+                reasons,
+            });
+        }
+    }
+    recommendations.sort_by(|a, b| b.usable.cmp(&a.usable).then(a.penalty.total_cmp(&b.penalty)));
+    recommendations
+}
+
+/// Picks the die with the largest V opening that still supports the given flange,
+/// since a wider V keeps the tonnage down.
+pub fn suggest_die_for_flange(dies: &[Die], flange_mm: f64) -> Option<&Die> {
+    dies.iter()
+        .filter(|d| geometry::min_flange_for_die(d) <= flange_mm)
+        .max_by(|a, b| a.v_opening_mm.total_cmp(&b.v_opening_mm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{self, BendDirection, BendFeature, BendMethod, BendStep, MaterialName, SheetMetal};
+
+    fn die(v_opening_mm: f64) -> Die {
+        Die { name: format!("V{}", v_opening_mm), v_opening_mm, angle_deg: 85.0, shoulder_radius_mm: 2.0 }
+    }
+
+    fn punch() -> Punch {
+        Punch { name: "P85.R05".to_string(), height_mm: 60.0, angle_deg: 85.0, radius_mm: 0.5 }
+    }
+
+    fn job(thickness_mm: f64, width_mm: f64) -> Job {
+        Job {
+            sheet: SheetMetal { original_length_mm: 500.0, width_mm, thickness_mm, ..SheetMetal::default() },
+            steps: Vec::new(),
+            ..Job::default()
+        }
+    }
+
+    #[test]
+    fn prefers_a_v_opening_of_eight_times_the_thickness() {
+        let materials = model::default_materials();
+        let steel = &materials[&MaterialName::Steel];
+        let dies = [die(12.0), die(24.0), die(16.0), die(20.0)];
+        // Ask for the radius the V16 gives, so only the V/t ratio tells them apart
+        let radius = physics::air_bend_inner_radius_mm(&dies[2], steel);
+        let ranked = recommend_tooling(&job(2.0, 100.0), steel, &[punch()], &dies, radius, 90.0);
+        assert_eq!(ranked.len(), 4);
+        assert_eq!(ranked.iter().map(|r| r.die_idx).collect::<Vec<_>>(), vec![2, 3, 0, 1]);
+        assert!(ranked.iter().all(|r| r.usable));
+        assert!(ranked.windows(2).all(|w| w[0].penalty <= w[1].penalty));
+    }
+
+    #[test]
+    fn dies_outside_the_ratio_range_are_not_usable() {
+        let materials = model::default_materials();
+        let steel = &materials[&MaterialName::Steel];
+        let ranked = recommend_tooling(&job(2.0, 100.0), steel, &[punch()], &[die(8.0), die(16.0), die(32.0)], 2.0, 90.0);
+        assert_eq!(ranked[0].die_idx, 1);
+        assert_eq!(ranked.iter().filter(|r| r.usable).count(), 1);
+    }
+
+    #[test]
+    fn tooling_over_press_capacity_is_not_usable() {
+        let materials = model::default_materials();
+        let steel = &materials[&MaterialName::Steel];
+        // 10 mm steel, 3 m wide in a V80: about 1870 kN
+        let ranked = recommend_tooling(&job(10.0, 3000.0), steel, &[punch()], &[die(80.0)], 12.0, 90.0);
+        let recommendation = &ranked[0];
+        assert!(recommendation.estimated_force_kn > physics::PRESS_CAPACITY_KN);
+        assert!(!recommendation.usable);
+        assert!(recommendation.reasons.iter().any(|r| matches!(r, RecommendationReason::Force { force_kn, capacity_kn } if force_kn > capacity_kn)));
+    }
+
+    #[test]
+    fn tools_must_close_past_the_target_angle() {
+        let materials = model::default_materials();
+        let steel = &materials[&MaterialName::Steel];
+        let ranked = recommend_tooling(&job(2.0, 100.0), steel, &[punch()], &[die(16.0)], 2.0, 80.0);
+        assert!(!ranked[0].usable);
+        assert!(ranked[0].reasons.iter().any(|r| matches!(r, RecommendationReason::ToolAngleTooOpen { .. })));
+    }
+
+    #[test]
+    fn short_flanges_rule_out_wide_dies() {
+        let materials = model::default_materials();
+        let steel = &materials[&MaterialName::Steel];
+        let mut job = job(2.0, 100.0);
+        job.steps = [(1, 12.0), (2, 250.0)].into_iter().map(|(sequence_order, position_mm)| BendStep {
+            sequence_order,
+            position_mm,
+            target_angle_deg: 90.0,
+            radius_mm: 2.0,
+            direction: BendDirection::Up,
+            method: BendMethod::AirBending,
+            feature: BendFeature::Single,
+        }).collect();
+        // A 12 mm flange sits on a V16 (min 11.2 mm) but slips into a V20 (min 14 mm)
+        let ranked = recommend_tooling(&job, steel, &[punch()], &[die(20.0), die(16.0)], 2.0, 90.0);
+        let v20 = ranked.iter().find(|r| r.die_idx == 0).unwrap();
+        assert!(!v20.usable);
+        assert!(v20.reasons.iter().any(|r| matches!(r, RecommendationReason::FlangeTooShort { flange_mm, .. } if *flange_mm == 12.0)));
+        assert!(ranked.iter().find(|r| r.die_idx == 1).unwrap().usable);
+    }
+
+    #[test]
+    fn suggests_the_widest_die_the_flange_bridges() {
+        let dies = model::default_dies(); // V12, V20, V16, V16
+        assert_eq!(suggest_die_for_flange(&dies, 15.0).map(|d| d.v_opening_mm), Some(20.0));
+        assert_eq!(suggest_die_for_flange(&dies, 12.0).map(|d| d.v_opening_mm), Some(16.0));
+        assert_eq!(suggest_die_for_flange(&dies, 10.0).map(|d| d.v_opening_mm), Some(12.0));
+        assert_eq!(suggest_die_for_flange(&dies, 5.0), None);
+    }
+}
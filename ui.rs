@@ -1,64 +1,137 @@
-use crate::state::{AppState, BendDirection, MaterialName}; // MaterialName is used for to_string
+use crate::i18n::{self, tr, trf, Language};
+use crate::message_log::LogLevel;
+use crate::numeric_input::{NumericInput, Quantity};
+use crate::state::{self, AppState, PendingJobAction, UnsavedChangesChoice};
 use crate::logic;
-use egui::{Context, Ui, RichText, Color32, ComboBox, ScrollArea, TextEdit, Vec2}; // Color32 is used for status_message
+use vouwbank::cycle_time::MachineSpeeds;
+use vouwbank::geometry;
+use vouwbank::handling::{self, PartSupport};
+use vouwbank::model::{BendDirection, BendFeatureKind, BendMethod, RollingDirection};
+use vouwbank::nesting::{self, Nest};
+use vouwbank::physics;
+use vouwbank::production::Shift;
+use vouwbank::tooling;
+use vouwbank::units::{GaugeStandard, LengthUnit};
+use egui::{Context, Ui, RichText, Color32, ComboBox, ScrollArea, TextEdit, Vec2, Pos2, Rect, Stroke, Key, KeyboardShortcut, Modifiers};
+
+const UNDO_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+const REDO_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Y);
+
+/// Text field for a numeric input, validated as you type: red outline and the
+/// reason below while it has no valid value, the evaluated value for expressions.
+fn numeric_field(
+    ui: &mut Ui,
+    state: &mut AppState,
+    field: fn(&mut AppState) -> &mut NumericInput,
+    evaluate: fn(&AppState, &NumericInput) -> Result<f64, String>,
+    hint: Option<&str>,
+) {
+    let (lang, unit) = (state.language, state.length_unit);
+    ui.vertical(|ui| {
+        let mut text_edit = TextEdit::singleline(&mut field(state).text).desired_width(100.0);
+        if let Some(hint) = hint {
+            text_edit = text_edit.hint_text(hint);
+        }
+        let response = ui.add(text_edit);
+        let input = field(state).clone();
+        match evaluate(state, &input) {
+            Ok(value) if input.is_expression() => {
+                let shown = match input.quantity {
+                    Quantity::Length => unit.format(value, 2),
+                    Quantity::Angle => format!("{:.2}°", value),
+                };
+                ui.label(RichText::new(trf(lang, "input.evaluated", &[&shown])).small().weak());
+            }
+            Ok(_) => {}
+            Err(e) => {
+                ui.painter().rect_stroke(response.rect.expand(1.0), 2.0, Stroke::new(1.0, Color32::RED));
+                ui.label(RichText::new(e).small().color(Color32::RED));
+            }
+        }
+    });
+}
 
 fn sheet_properties_panel(ui: &mut Ui, state: &mut AppState) {
-    ui.strong("Plaat Eigenschappen");
+    let (lang, unit) = (state.language, state.length_unit);
+    ui.strong(tr(lang, "sheet.title"));
     ui.group(|ui| {
         egui::Grid::new("sheet_properties_grid")
             .num_columns(2)
             .spacing([10.0, 4.0])
             .striped(true)
             .show(ui, |ui| {
-                ui.label("Lengte (mm):");
-                ui.add(TextEdit::singleline(&mut state.sheet_input.length_mm_str).desired_width(100.0));
+                ui.label(trf(lang, "sheet.length", &[&unit.symbol()]));
+                numeric_field(ui, state, |s| &mut s.sheet_input.length, logic::input_value, None);
                 ui.end_row();
 
-                ui.label("Dikte (mm):");
-                ui.add(TextEdit::singleline(&mut state.sheet_input.thickness_mm_str).desired_width(100.0));
+                ui.label(trf(lang, "sheet.thickness", &[&unit.symbol()]));
+                numeric_field(ui, state, |s| &mut s.sheet_input.thickness, logic::thickness_input_value, Some(tr(lang, "sheet.thickness_hint")));
                 ui.end_row();
 
-                ui.label("Breedte (mm):");
-                ui.add(TextEdit::singleline(&mut state.sheet_input.width_mm_str).desired_width(100.0));
+                ui.label(trf(lang, "sheet.width", &[&unit.symbol()]));
+                numeric_field(ui, state, |s| &mut s.sheet_input.width, logic::input_value, None);
                 ui.end_row();
 
-                ui.label("Materiaal:");
+                ui.label(tr(lang, "sheet.material"));
                 ComboBox::from_id_source("material_select_cnc")
                     .selected_text(
                         state.material_display_order
                             .get(state.sheet_input.selected_material_idx)
-                            .map_or_else(|| "N/A".to_string(), |m| m.to_string())
+                            .map_or_else(|| tr(lang, "common.na").to_string(), |m| i18n::material_name(lang, m))
                     )
                     .width(150.0)
                     .show_index(
                         ui,
                         &mut state.sheet_input.selected_material_idx,
                         state.material_display_order.len(),
-                        |i| state.material_display_order[i].to_string()
+                        |i| i18n::material_name(lang, &state.material_display_order[i])
                     );
                 ui.end_row();
+
+                ui.label(tr(lang, "sheet.rolling")).on_hover_text(tr(lang, "sheet.rolling_hint"));
+                let mut rolling = state.tab().job.sheet.rolling_direction;
+                ComboBox::from_id_source("rolling_direction_select_cnc")
+                    .selected_text(i18n::rolling_direction_name(lang, rolling))
+                    .width(150.0)
+                    .show_ui(ui, |ui| {
+                        for option in RollingDirection::all() {
+                            ui.selectable_value(&mut rolling, option, i18n::rolling_direction_name(lang, option));
+                        }
+                    });
+                if rolling != state.tab().job.sheet.rolling_direction {
+                    logic::set_rolling_direction(state, rolling);
+                }
+                ui.end_row();
             });
         
         ui.add_space(5.0);
-        if ui.button("Update Plaat Eigenschappen").clicked() {
+        if ui.button(tr(lang, "sheet.update")).clicked() {
             logic::update_sheet_properties(state);
         }
         if let Some(min_rad) = logic::get_recommended_min_bend_radius(state) {
-            ui.label(RichText::new(format!("Recommended Min Bend Radius: {:.2} mm", min_rad)).small());
+            ui.label(RichText::new(trf(lang, "sheet.min_radius", &[&unit.format(min_rad, 2)])).small());
+        }
+        if unit == LengthUnit::Inch {
+            let sheet = &state.tab().job.sheet;
+            let standard = GaugeStandard::for_material(&sheet.material_name);
+            if let Some(gauge) = standard.gauge_for_thickness(sheet.thickness_mm) {
+                ui.label(RichText::new(trf(lang, "sheet.gauge", &[&gauge, &standard])).small());
+            }
         }
     });
 }
 
 fn tooling_setup_panel(ui: &mut Ui, state: &mut AppState) {
-    ui.strong("Tooling Setup");
+    let (lang, unit) = (state.language, state.length_unit);
+    ui.strong(tr(lang, "tooling.title"));
      ui.group(|ui| {
         ui.horizontal(|ui| {
-            ui.label("Punch:");
+            ui.label(tr(lang, "tooling.punch"));
             ComboBox::from_id_source("punch_select_cnc")
                 .selected_text(
                     state.available_punches
                         .get(state.tooling_input.selected_punch_idx)
-                        .map_or_else(|| "N/A".to_string(), |p| p.name.clone())
+                        .map_or_else(|| tr(lang, "common.na").to_string(), |p| p.name.clone())
                 )
                 .width(150.0)
                 .show_index(
@@ -69,12 +142,12 @@ fn tooling_setup_panel(ui: &mut Ui, state: &mut AppState) {
                 );
         });
         ui.horizontal(|ui|{
-            ui.label("Die:    "); // Padding for alignment
+            ui.label(tr(lang, "tooling.die"));
             ComboBox::from_id_source("die_select_cnc")
                 .selected_text(
                     state.available_dies
                         .get(state.tooling_input.selected_die_idx)
-                        .map_or_else(|| "N/A".to_string(), |d| d.name.clone())
+                        .map_or_else(|| tr(lang, "common.na").to_string(), |d| d.name.clone())
                 )
                 .width(150.0)
                 .show_index(
@@ -86,165 +159,941 @@ fn tooling_setup_panel(ui: &mut Ui, state: &mut AppState) {
         });
         ui.add_space(5.0);
         if let Some(punch) = state.available_punches.get(state.tooling_input.selected_punch_idx) {
-            ui.label(RichText::new(format!("Selected Punch: {} (Angle: {}°, Radius: {}mm)", punch.name, punch.angle_deg, punch.radius_mm)).small());
+            ui.label(RichText::new(trf(lang, "tooling.selected_punch", &[&punch.name, &punch.angle_deg, &unit.format(punch.radius_mm, 1)])).small());
         }
         if let Some(die) = state.available_dies.get(state.tooling_input.selected_die_idx) {
-            ui.label(RichText::new(format!("Selected Die: {} (V-Open: {}mm, Angle: {}°)", die.name, die.v_opening_mm, die.angle_deg)).small());
+            ui.label(RichText::new(trf(lang, "tooling.selected_die", &[&die.name, &unit.format(die.v_opening_mm, 1), &die.angle_deg])).small());
+            ui.label(RichText::new(trf(lang, "tooling.min_flange", &[&unit.format(geometry::min_flange_for_die(die), 1)])).small());
+        }
+        let ideal_v = physics::ideal_v_opening_mm(state.tab().job.sheet.thickness_mm);
+        ui.label(RichText::new(trf(lang, "tooling.ideal_v", &[&unit.format(ideal_v, 1)])).small());
+        ui.add_space(5.0);
+        if ui.button(tr(lang, "tooling.recommend")).clicked() {
+            logic::update_tooling_recommendations(state);
+        }
+        let mut apply_idx = None;
+        for (i, rec) in state.tooling_recommendations.iter().enumerate().take(3) {
+            ui.horizontal(|ui| {
+                let text = format!("{}. {} / {}", i + 1, state.available_punches[rec.punch_idx].name, state.available_dies[rec.die_idx].name);
+                let color = if rec.usable { ui.style().visuals.text_color() } else { Color32::GRAY };
+                ui.label(RichText::new(text).small().color(color))
+                    .on_hover_text(rec.reasons.iter().map(|r| i18n::recommendation_reason(lang, unit, r)).collect::<Vec<_>>().join("\n"));
+                if rec.usable && ui.small_button(tr(lang, "tooling.use")).clicked() {
+                    apply_idx = Some(i);
+                }
+            });
+        }
+        if let Some(i) = apply_idx {
+            logic::apply_tooling_recommendation(state, i);
+        }
+        for warning in logic::selected_die_flange_warnings(state) {
+            let suggestion = tooling::suggest_die_for_flange(&state.available_dies, warning.flange_mm)
+                .map_or_else(|| tr(lang, "tooling.no_suitable_die").to_string(), |d| trf(lang, "tooling.try_die", &[&d.name]));
+            ui.label(RichText::new(trf(lang, "tooling.flange_warning", &[
+                &warning.sequence_order, &unit.format(warning.flange_mm, 1), &unit.format(warning.min_flange_mm, 1), &suggestion,
+            ])).small().color(Color32::RED));
         }
     });
 }
 
 fn bend_definition_panel(ui: &mut Ui, state: &mut AppState) {
-    ui.strong("Defieër Buig Stap");
+    let (lang, unit) = (state.language, state.length_unit);
+    ui.strong(tr(lang, "bend.title"));
     ui.group(|ui| {
          egui::Grid::new("bend_def_grid_cnc")
             .num_columns(2)
             .spacing([10.0, 4.0])
             .striped(true)
             .show(ui, |ui| {
-                ui.label("Positie (mm):");
-                ui.add(TextEdit::singleline(&mut state.bend_input.position_mm_str).desired_width(100.0));
+                ui.label(trf(lang, "bend.position", &[&unit.symbol()]));
+                numeric_field(ui, state, |s| &mut s.bend_input.position, logic::input_value, None);
                 ui.end_row();
 
-                ui.label("Gewenste Hoek (°):");
-                ui.add(TextEdit::singleline(&mut state.bend_input.target_angle_deg_str).desired_width(100.0));
+                ui.label(tr(lang, "bend.angle"));
+                numeric_field(ui, state, |s| &mut s.bend_input.target_angle, logic::input_value, None);
                 ui.end_row();
 
-                ui.label("Binnen Straal (mm):");
-                ui.add(TextEdit::singleline(&mut state.bend_input.radius_mm_str).desired_width(100.0));
+                ui.label(trf(lang, "bend.radius", &[&unit.symbol()]));
+                numeric_field(ui, state, |s| &mut s.bend_input.radius, logic::input_value, None);
                 ui.end_row();
 
-                ui.label("Richting:");
+                ui.label(tr(lang, "bend.direction"));
                  ComboBox::from_id_source("bend_direction_select_cnc")
                     .selected_text(
                         BendDirection::default_directions()
                             .get(state.bend_input.selected_direction_idx)
-                            .map_or_else(|| "N/A".to_string(), |d| d.to_string())
+                            .map_or(tr(lang, "common.na"), |d| i18n::direction_name(lang, d))
                     )
                     .width(100.0)
                     .show_index(
                         ui,
                         &mut state.bend_input.selected_direction_idx,
                         BendDirection::default_directions().len(),
-                        |i| BendDirection::default_directions()[i].to_string()
+                        |i| i18n::direction_name(lang, &BendDirection::default_directions()[i])
+                    );
+                ui.end_row();
+
+                ui.label(tr(lang, "bend.feature"));
+                ComboBox::from_id_source("bend_feature_select_cnc")
+                    .selected_text(
+                        BendFeatureKind::all()
+                            .get(state.bend_input.selected_feature_idx)
+                            .map_or(tr(lang, "common.na"), |k| i18n::feature_kind_name(lang, *k))
+                    )
+                    .width(100.0)
+                    .show_index(
+                        ui,
+                        &mut state.bend_input.selected_feature_idx,
+                        BendFeatureKind::all().len(),
+                        |i| i18n::feature_kind_name(lang, BendFeatureKind::all()[i])
+                    );
+                ui.end_row();
+
+                if logic::selected_feature_kind(state) == BendFeatureKind::Joggle {
+                    ui.label(trf(lang, "bend.joggle_offset", &[&unit.symbol()]));
+                    numeric_field(ui, state, |s| &mut s.bend_input.joggle_offset, logic::input_value, None);
+                    ui.end_row();
+                }
+
+                ui.label(tr(lang, "bend.method"));
+                ComboBox::from_id_source("bend_method_select_cnc")
+                    .selected_text(
+                        BendMethod::default_methods()
+                            .get(state.bend_input.selected_method_idx)
+                            .map_or(tr(lang, "common.na"), |m| i18n::method_name(lang, *m))
+                    )
+                    .width(100.0)
+                    .show_index(
+                        ui,
+                        &mut state.bend_input.selected_method_idx,
+                        BendMethod::default_methods().len(),
+                        |i| i18n::method_name(lang, BendMethod::default_methods()[i])
                     );
                 ui.end_row();
             });
         ui.add_space(5.0);
-        if ui.button("Voeg Buiging Toe Aan De Job").clicked() {
+        if ui.button(tr(lang, "bend.add")).clicked() {
             logic::add_bend_step(state);
         }
     });
 }
 
 fn bend_sequence_panel(ui: &mut Ui, state: &mut AppState) {
-    ui.strong(format!("Huidge Job Buig Sequentie ({})", state.current_job.steps.len()));
+    let (lang, unit) = (state.language, state.length_unit);
+    ui.strong(trf(lang, "sequence.title", &[&state.tab().job.steps.len()]));
     ui.group(|ui| {
         ScrollArea::vertical().max_height(150.0).min_scrolled_height(100.0).show(ui, |ui| {
-            if state.current_job.steps.is_empty() {
-                ui.label("Geen buig stappen gedefinieërd voor de huidige job.");
+            if state.tab().job.steps.is_empty() {
+                ui.label(tr(lang, "sequence.empty"));
             } else {
                 egui::Grid::new("bend_sequence_grid_cnc")
-                    .num_columns(5) // #, Pos, Angle, Radius, Dir
+                    .num_columns(8) // #, Pos, Angle, Radius, Dir, Method, Feature, Support
                     .spacing([5.0, 2.0])
                     .striped(true)
                     .show(ui, |ui| {
                         ui.label(RichText::new("#").strong());
-                        ui.label(RichText::new("Pos").strong());
-                        ui.label(RichText::new("Hoek").strong());
-                        ui.label(RichText::new("Graden").strong());
-                        ui.label(RichText::new("Dir").strong());
+                        ui.label(RichText::new(trf(lang, "sequence.col_position", &[&unit.symbol()])).strong());
+                        ui.label(RichText::new(tr(lang, "sequence.col_angle")).strong());
+                        ui.label(RichText::new(trf(lang, "sequence.col_radius", &[&unit.symbol()])).strong());
+                        for key in ["sequence.col_direction", "sequence.col_method", "sequence.col_feature", "sequence.col_support"] {
+                            ui.label(RichText::new(tr(lang, key)).strong());
+                        }
                         ui.end_row();
 
-                        for step in &state.current_job.steps {
-                            ui.label(step.sequence_order.to_string());
-                            ui.label(format!("{:.1}", step.position_mm));
+                        let lifts = logic::flange_lifts(state);
+                        let tab = &mut state.tabs[state.active_tab];
+                        for (index, step) in tab.job.steps.iter().enumerate() {
+                            let selected = tab.selected_steps.contains(&index);
+                            if ui.selectable_label(selected, step.sequence_order.to_string()).on_hover_text(tr(lang, "sequence.select_hint")).clicked() {
+                                if selected {
+                                    tab.selected_steps.remove(&index);
+                                } else {
+                                    tab.selected_steps.insert(index);
+                                }
+                            }
+                            ui.label(unit.format_value(step.position_mm, 1));
                             ui.label(format!("{:.1}", step.target_angle_deg));
-                            ui.label(format!("{:.1}", step.radius_mm));
-                            ui.label(step.direction.to_string());
+                            ui.label(unit.format_value(step.radius_mm, 1));
+                            ui.label(i18n::direction_name(lang, &step.direction));
+                            ui.label(i18n::method_name(lang, step.method));
+                            ui.label(i18n::feature_name(lang, unit, &step.feature));
+                            match lifts.iter().find(|l| l.sequence_order == step.sequence_order) {
+                                Some(lift) => {
+                                    let mut text = RichText::new(i18n::support_name(lang, lift.support));
+                                    if lift.support != PartSupport::Operator {
+                                        text = text.color(Color32::YELLOW);
+                                    }
+                                    ui.label(text).on_hover_text(trf(lang, "support.detail", &[
+                                        &unit.format(lift.flange_mm, 0), &format!("{:.1}", lift.flange_mass_kg),
+                                        &format!("{:.2}", lift.tip_speed_m_s), &format!("{:.0}", lift.moment_nm),
+                                    ]));
+                                }
+                                None => { ui.label(tr(lang, "common.na")); }
+                            }
                             ui.end_row();
                         }
                     });
             }
         });
         ui.add_space(5.0);
-        if ui.button("Wis Alle Plooi Stappen").clicked() {
-            logic::clear_all_bend_steps(state);
-        }
+        ui.horizontal(|ui| {
+            if ui.button(tr(lang, "sequence.clear")).clicked() {
+                logic::clear_all_bend_steps(state);
+            }
+            if ui.button(tr(lang, "menu.copy_steps")).clicked() {
+                logic::copy_bend_steps(state);
+            }
+            if ui.add_enabled(!state.step_clipboard.is_empty(), egui::Button::new(trf(lang, "menu.paste_steps", &[&state.step_clipboard.len()]))).clicked() {
+                logic::paste_bend_steps(state);
+            }
+        });
     });
 }
 
-fn execution_panel(ui: &mut Ui, state: &mut AppState, ctx: &Context) {
-    ui.strong("Machine Bediening");
+fn execution_panel(ui: &mut Ui, state: &mut AppState) {
+    let lang = state.language;
+    ui.strong(tr(lang, "exec.title"));
     ui.group(|ui| {
-        if ui.button("Voer Simulatie Uit & Genereer Profiel").clicked() {
-            logic::run_simulation(state, ctx);
+        if ui.button(tr(lang, "exec.run")).clicked() {
+            logic::run_simulation(state);
         }
         ui.add_space(5.0);
-        ui.label(format!("Machine Status: {}", state.simulation_status));
-        ui.label(format!("Geplooide Onderdelen Deze Sessie: {}", state.parts_bent_session));
+        ui.label(trf(lang, "exec.status", &[&tr(lang, state.tab().simulation_status)]));
+        let (shift, date) = Shift::at(chrono::Local::now().naive_local());
+        let shift_total = state.production.queue.shift_total(shift, date);
+        ui.label(trf(lang, "exec.parts_bent", &[&i18n::shift_name(lang, shift), &shift_total]));
+        cycle_time_section(ui, state);
+        handling_section(ui, state);
     });
 }
 
+/// "42.5 s", or "2:05 min" from a minute up.
+fn format_duration(seconds: f64) -> String {
+    if seconds < 60.0 {
+        format!("{:.1} s", seconds)
+    } else {
+        let rounded = seconds.round() as u64;
+        format!("{}:{:02} min", rounded / 60, rounded % 60)
+    }
+}
+
+fn cycle_time_section(ui: &mut Ui, state: &AppState) {
+    let lang = state.language;
+    let Some(cycle) = logic::cycle_time_estimate(state) else { return };
+    if cycle.steps.is_empty() {
+        return;
+    }
+    ui.add_space(5.0);
+    ui.label(RichText::new(trf(lang, "cycle.total", &[&format_duration(cycle.total_s())])).strong());
+    egui::CollapsingHeader::new(tr(lang, "cycle.details")).id_source("cycle_time_details").show(ui, |ui| {
+        egui::Grid::new("cycle_time_grid").num_columns(7).striped(true).show(ui, |ui| {
+            for key in ["cycle.col_step", "cycle.col_hits", "cycle.col_backgauge", "cycle.col_handling", "cycle.col_tools", "cycle.col_ram", "cycle.col_total"] {
+                ui.label(RichText::new(tr(lang, key)).strong());
+            }
+            ui.end_row();
+            for step in &cycle.steps {
+                ui.label(step.sequence_order.to_string());
+                ui.label(step.hits.to_string());
+                for seconds in [step.backgauge_s, step.handling_s, step.tool_change_s, step.ram_s, step.total_s()] {
+                    ui.label(format!("{:.1}", seconds));
+                }
+                ui.end_row();
+            }
+        });
+        ui.label(RichText::new(trf(lang, "cycle.summary", &[&format_duration(cycle.load_unload_s), &cycle.flips, &cycle.rotations, &cycle.tool_changes])).small());
+    });
+}
+
+fn handling_section(ui: &mut Ui, state: &AppState) {
+    let (lang, unit) = (state.language, state.length_unit);
+    let Some(part) = logic::part_handling(state) else { return };
+    if part.steps.is_empty() {
+        return;
+    }
+    ui.add_space(5.0);
+    ui.label(RichText::new(trf(lang, "handling.mass", &[&format!("{:.1}", part.mass_kg)])).strong());
+    if part.over_handling_limit() {
+        ui.label(RichText::new(trf(lang, "handling.over_limit", &[&format!("{:.0}", handling::SINGLE_OPERATOR_LIMIT_KG)])).color(Color32::YELLOW));
+    }
+    egui::CollapsingHeader::new(tr(lang, "handling.details")).id_source("handling_details").show(ui, |ui| {
+        egui::Grid::new("handling_grid").num_columns(4).striped(true).show(ui, |ui| {
+            for key in ["handling.col_step", "handling.col_cog", "handling.col_overhang"] {
+                ui.label(RichText::new(tr(lang, key)).strong());
+            }
+            ui.label("");
+            ui.end_row();
+            for step in &part.steps {
+                ui.label(step.sequence_order.to_string());
+                ui.label(format!("{}, {}", unit.format(step.cog_mm[0], 1), unit.format(step.cog_mm[1], 1)));
+                ui.label(unit.format(step.overhang_mm, 1));
+                if step.tips_away {
+                    ui.label(RichText::new(tr(lang, "handling.tips_away")).color(Color32::YELLOW));
+                } else {
+                    ui.label(tr(lang, "handling.balanced"));
+                }
+                ui.end_row();
+            }
+        });
+        ui.label(RichText::new(trf(lang, "handling.final_cog", &[&unit.format(part.cog_mm[0], 1), &unit.format(part.cog_mm[1], 1)])).small());
+    });
+}
+
+/// Paints a profile centre line scaled to fit `rect`, y pointing up.
+pub fn paint_profile(painter: &egui::Painter, rect: Rect, points: &[[f64; 2]], stroke: Stroke) {
+    if points.len() < 2 {
+        return;
+    }
+    let Some([min_x, min_y, max_x, max_y]) = geometry::profile_bounds(points) else { return };
+    let span = (max_x - min_x).max(max_y - min_y).max(1e-6);
+    let scale = (rect.width().min(rect.height()) as f64 * 0.9) / span;
+    let center = rect.center();
+    let mid = [(min_x + max_x) / 2.0, (min_y + max_y) / 2.0];
+    let screen_points: Vec<Pos2> = points.iter()
+        .map(|[x, y]| Pos2::new(
+            center.x + ((x - mid[0]) * scale) as f32,
+            center.y - ((y - mid[1]) * scale) as f32,
+        ))
+        .collect();
+    painter.add(egui::Shape::line(screen_points, stroke));
+}
+
 fn profile_display_panel(ui: &mut Ui, state: &mut AppState) {
-    ui.strong("Simulatie Profiel Plaat");
+    ui.strong(tr(state.language, "profile.title"));
     ui.group(|ui| {
         let desired_height = ui.available_height().max(200.0);
         ui.allocate_ui(Vec2::new(ui.available_width(), desired_height), |ui_inner| {
             ui_inner.centered_and_justified(|ui_centered| {
-                if let (Some(texture), Some(size_val)) = (&state.simulated_profile_texture, state.simulated_profile_size) {
-                    // Corrected: No dereference needed for size_val as it's already Vec2
-                    let mut display_size = size_val; 
-                    let aspect_ratio = size_val.x / size_val.y;
-
-                    if display_size.x > ui_centered.available_width() {
-                        display_size.x = ui_centered.available_width();
-                        display_size.y = display_size.x / aspect_ratio;
-                    }
-                    if display_size.y > ui_centered.available_height() {
-                        display_size.y = ui_centered.available_height();
-                        display_size.x = display_size.y * aspect_ratio;
-                    }
-                    ui_centered.image((texture.id(), display_size));
+                if let Some(result) = &state.tab().simulation_result {
+                    let (rect, _) = ui_centered.allocate_exact_size(ui_centered.available_size(), egui::Sense::hover());
+                    let stroke = Stroke::new(2.0, ui_centered.style().visuals.strong_text_color());
+                    paint_profile(ui_centered.painter(), rect, &result.profile, stroke);
                 } else {
-                    ui_centered.label(&state.profile_load_status);
+                    ui_centered.label(tr(state.language, state.tab().profile_load_status));
                 }
             });
         });
     });
 }
 
-fn status_bar(ui: &mut Ui, state: &AppState) {
-    let (text, color_opt) = &state.status_message; // Renamed color to color_opt for clarity
-    let rich_text = if let Some(c) = color_opt { // c is &Color32
-        RichText::new(text).color(*c) // Dereference c
-    } else {
-        RichText::new(text).color(ui.style().visuals.text_color())
-    };
+fn status_bar(ui: &mut Ui, state: &mut AppState) {
     ui.separator();
-    ui.label(rich_text);
+    ui.horizontal(|ui| {
+        ui.toggle_value(&mut state.show_message_log, trf(state.language, "log.toggle", &[&state.message_log.entries.len()]));
+        if let Some(entry) = state.message_log.latest() {
+            let color = entry.level.color().unwrap_or(ui.style().visuals.text_color());
+            ui.label(RichText::new(entry.time.format("%H:%M:%S").to_string()).small().weak());
+            ui.label(RichText::new(&entry.text).color(color));
+        }
+    });
+}
+
+fn message_log_panel(ui: &mut Ui, state: &mut AppState) {
+    let lang = state.language;
+    let log = &mut state.message_log;
+    let mut export = false;
+    ui.horizontal(|ui| {
+        ui.strong(tr(lang, "log.title"));
+        ui.separator();
+        for level in LogLevel::all() {
+            let mut shown = log.shown_levels.contains(&level);
+            if ui.checkbox(&mut shown, level.to_string()).changed() {
+                if shown {
+                    log.shown_levels.push(level);
+                } else {
+                    log.shown_levels.retain(|l| *l != level);
+                }
+            }
+        }
+        ui.separator();
+        ui.add(TextEdit::singleline(&mut log.search).hint_text(tr(lang, "common.search")).desired_width(150.0));
+        if ui.button(tr(lang, "common.clear")).clicked() {
+            log.entries.clear();
+        }
+        export = ui.button(tr(lang, "log.export")).clicked();
+    });
+    ScrollArea::vertical().auto_shrink([false, false]).stick_to_bottom(true).show(ui, |ui| {
+        for entry in log.visible_entries() {
+            let color = entry.level.color().unwrap_or(ui.visuals().text_color());
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(entry.time.format("%H:%M:%S").to_string()).monospace().weak());
+                ui.label(RichText::new(format!("{:<5}", entry.level)).monospace().color(color));
+                ui.label(RichText::new(&entry.text).color(color));
+            });
+        }
+    });
+    if export {
+        logic::export_message_log(state);
+    }
+}
+
+fn production_window(ctx: &Context, state: &mut AppState) {
+    let lang = state.language;
+    let mut open = state.production.open;
+    let mut mark = None;
+    let mut remove = None;
+    let mut open_job = None;
+    egui::Window::new(tr(lang, "production.title"))
+        .open(&mut open)
+        .default_width(560.0)
+        .show(ctx, |ui| {
+            ui.strong(trf(lang, "production.new_order", &[&state.tab().job.name]));
+            egui::Grid::new("production_order_form").num_columns(2).show(ui, |ui| {
+                ui.label(tr(lang, "production.customer"));
+                ui.text_edit_singleline(&mut state.production.customer);
+                ui.end_row();
+                ui.label(tr(lang, "production.quantity"));
+                ui.add(TextEdit::singleline(&mut state.production.quantity).desired_width(60.0));
+                ui.end_row();
+                ui.label(tr(lang, "production.due_date"));
+                ui.add(TextEdit::singleline(&mut state.production.due_date).desired_width(100.0));
+                ui.end_row();
+            });
+            if ui.button(tr(lang, "production.add")).clicked() {
+                logic::add_production_order(state);
+            }
+            ui.separator();
+
+            let queue = &state.production.queue;
+            let today = chrono::Local::now().date_naive();
+            if queue.orders.is_empty() {
+                ui.label(tr(lang, "production.empty"));
+            } else {
+                ScrollArea::vertical().id_source("production_orders").max_height(240.0).show(ui, |ui| {
+                    egui::Grid::new("production_order_grid").num_columns(6).striped(true).show(ui, |ui| {
+                        for key in ["production.col_order", "production.col_customer", "production.col_job", "production.col_due", "production.col_progress"] {
+                            ui.label(RichText::new(tr(lang, key)).strong());
+                        }
+                        ui.end_row();
+                        for order in &queue.orders {
+                            let bent = queue.parts_bent(order.id);
+                            ui.label(order.id.to_string());
+                            ui.label(&order.customer);
+                            ui.label(&order.job_name);
+                            let due = order.due_date.format(state::DATE_FORMAT).to_string();
+                            if queue.is_overdue(order, today) {
+                                ui.label(RichText::new(format!("{} ({})", due, tr(lang, "production.overdue"))).color(Color32::RED));
+                            } else {
+                                ui.label(due);
+                            }
+                            ui.label(format!("{} / {}", bent, order.quantity));
+                            ui.horizontal(|ui| {
+                                if queue.is_complete(order) {
+                                    ui.label(RichText::new(tr(lang, "production.complete")).color(Color32::GREEN));
+                                } else {
+                                    if ui.small_button(tr(lang, "production.mark_one")).on_hover_text(tr(lang, "production.mark_one_hint")).clicked() {
+                                        mark = Some((order.id, 1));
+                                    }
+                                    if ui.small_button(tr(lang, "production.mark_rest")).on_hover_text(tr(lang, "production.mark_rest_hint")).clicked() {
+                                        mark = Some((order.id, queue.remaining(order)));
+                                    }
+                                }
+                                if let Some(path) = &order.job_file
+                                    && ui.small_button(tr(lang, "production.open_job")).on_hover_text(path.display().to_string()).clicked()
+                                {
+                                    open_job = Some(path.clone());
+                                }
+                                if ui.small_button(tr(lang, "production.remove")).clicked() {
+                                    remove = Some(order.id);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+            }
+            ui.separator();
+
+            ui.strong(tr(lang, "production.shift_totals"));
+            let totals = queue.shift_totals();
+            if totals.is_empty() {
+                ui.label(tr(lang, "production.no_history"));
+            }
+            ScrollArea::vertical().id_source("production_shifts").max_height(120.0).show(ui, |ui| {
+                for (date, shift, total) in totals {
+                    ui.label(trf(lang, "production.shift_row", &[&date.format(state::DATE_FORMAT), &i18n::shift_name(lang, shift), &total]));
+                }
+            });
+        });
+    state.production.open = open;
+    if let Some((order_id, count)) = mark {
+        logic::mark_parts_bent(state, order_id, count);
+    }
+    if let Some(order_id) = remove {
+        logic::remove_production_order(state, order_id);
+    }
+    if let Some(path) = open_job {
+        logic::request_job_action(ctx, state, PendingJobAction::OpenFile(path));
+    }
+}
+
+fn quote_window(ctx: &Context, state: &mut AppState) {
+    let lang = state.language;
+    let mut open = state.quote.open;
+    let mut export = None;
+    egui::Window::new(tr(lang, "quote.title"))
+        .open(&mut open)
+        .default_width(360.0)
+        .show(ctx, |ui| {
+            let material_name = state.tab().job.sheet.material_name.clone();
+            egui::Grid::new("quote_rates_grid").num_columns(2).show(ui, |ui| {
+                let rates = &mut state.quote.rates;
+                ui.label(tr(lang, "quote.quantity"));
+                ui.add(egui::DragValue::new(&mut state.quote.quantity).clamp_range(1..=u32::MAX));
+                ui.end_row();
+                ui.label(tr(lang, "quote.machine_rate"));
+                ui.add(egui::DragValue::new(&mut rates.machine_rate_per_hour).clamp_range(0.0..=f64::MAX).speed(0.5));
+                ui.end_row();
+                ui.label(tr(lang, "quote.setup_minutes"));
+                ui.add(egui::DragValue::new(&mut rates.setup_minutes).clamp_range(0.0..=f64::MAX).speed(0.5));
+                ui.end_row();
+                ui.label(tr(lang, "quote.currency"));
+                ui.add(TextEdit::singleline(&mut rates.currency).desired_width(60.0));
+                ui.end_row();
+                if let Some(material) = state.available_materials.get_mut(&material_name) {
+                    ui.label(trf(lang, "quote.price_per_kg", &[&i18n::material_name(lang, &material_name)]));
+                    ui.add(egui::DragValue::new(&mut material.price_per_kg).clamp_range(0.0..=f64::MAX).speed(0.01).fixed_decimals(2));
+                    ui.end_row();
+                }
+            });
+            ui.separator();
+            match logic::current_quote(state) {
+                Some(quote) => {
+                    for line in logic::quote_lines(state, &quote) {
+                        ui.label(line);
+                    }
+                }
+                None => { ui.label(tr(lang, "tooling.none_selected")); }
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button(tr(lang, "quote.export_csv")).clicked() { export = Some(false); }
+                if ui.button(tr(lang, "quote.export_pdf")).clicked() { export = Some(true); }
+            });
+        });
+    state.quote.open = open;
+    if let Some(pdf) = export {
+        logic::export_quote(state, pdf);
+    }
 }
 
-fn file_menu(ui: &mut Ui, state: &mut AppState, _ctx: &Context) {
-    ui.menu_button("Bestand", |ui| {
-        if ui.button("Laad Taak...").clicked() {
-            logic::handle_load_job(state, Some("jobs/sample_job.json".to_string()));
+/// Editor for the axis speeds and handling times behind the cycle time, the
+/// quote and the flange lift.
+fn machine_speeds_window(ctx: &Context, state: &mut AppState) {
+    let lang = state.language;
+    let mut open = state.show_machine_speeds;
+    egui::Window::new(tr(lang, "speeds.title"))
+        .open(&mut open)
+        .default_width(300.0)
+        .show(ctx, |ui| {
+            let speeds = &mut state.machine_speeds;
+            egui::Grid::new("machine_speeds_grid").num_columns(2).show(ui, |ui| {
+                // Speeds are divisors, so they stay positive
+                for (key, value, min) in [
+                    ("speeds.approach", &mut speeds.approach_mm_s, 1.0),
+                    ("speeds.bending", &mut speeds.bending_mm_s, 0.1),
+                    ("speeds.return", &mut speeds.return_mm_s, 1.0),
+                    ("speeds.opening", &mut speeds.opening_mm, 0.0),
+                    ("speeds.dwell", &mut speeds.dwell_s, 0.0),
+                    ("speeds.backgauge", &mut speeds.backgauge_mm_s, 1.0),
+                    ("speeds.backgauge_settle", &mut speeds.backgauge_settle_s, 0.0),
+                    ("speeds.tool_change", &mut speeds.tool_change_s, 0.0),
+                    ("speeds.flip", &mut speeds.flip_s, 0.0),
+                    ("speeds.rotate", &mut speeds.rotate_s, 0.0),
+                    ("speeds.load_unload", &mut speeds.load_unload_s, 0.0),
+                ] {
+                    ui.label(tr(lang, key));
+                    ui.add(egui::DragValue::new(value).clamp_range(min..=f64::MAX).speed(0.1));
+                    ui.end_row();
+                }
+            });
+            ui.separator();
+            if ui.button(tr(lang, "speeds.defaults")).clicked() {
+                *speeds = MachineSpeeds::default();
+            }
+        });
+    state.show_machine_speeds = open;
+}
+
+fn nest_window(ctx: &Context, state: &mut AppState) {
+    let (lang, unit) = (state.language, state.length_unit);
+    let mut open = state.nest.open;
+    let mut add_job = false;
+    let mut export = false;
+    egui::Window::new(tr(lang, "nest.title"))
+        .open(&mut open)
+        .default_width(480.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr(lang, "nest.quantity"));
+                ui.add(egui::DragValue::new(&mut state.nest.quantity).clamp_range(1..=u32::MAX));
+                add_job = ui.button(tr(lang, "nest.add_job")).on_hover_text(&state.tab().job.name).clicked();
+            });
+            if state.nest.blanks.is_empty() {
+                ui.label(tr(lang, "nest.empty"));
+            } else {
+                let mut remove = None;
+                egui::Grid::new("nest_blank_grid").num_columns(5).striped(true).show(ui, |ui| {
+                    ui.label(RichText::new(tr(lang, "nest.col_job")).strong());
+                    ui.label(RichText::new(tr(lang, "nest.col_stock")).strong());
+                    ui.label(RichText::new(trf(lang, "nest.col_blank", &[&unit.symbol()])).strong());
+                    ui.label(RichText::new(tr(lang, "nest.col_quantity")).strong());
+                    ui.end_row();
+                    for (index, blank) in state.nest.blanks.iter_mut().enumerate() {
+                        ui.label(&blank.job_name);
+                        ui.label(format!("{} {}", i18n::material_name(lang, &blank.material_name), unit.format(blank.thickness_mm, 2)));
+                        ui.label(format!("{} × {}", unit.format_value(blank.length_mm, 1), unit.format_value(blank.width_mm, 1)));
+                        ui.add(egui::DragValue::new(&mut blank.quantity).clamp_range(0..=u32::MAX));
+                        if ui.small_button(tr(lang, "production.remove")).clicked() {
+                            remove = Some(index);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(index) = remove {
+                    state.nest.blanks.remove(index);
+                }
+            }
+            ui.separator();
+
+            let plates = nesting::standard_plates();
+            ui.horizontal(|ui| {
+                ui.label(tr(lang, "nest.plate"));
+                ComboBox::from_id_source("nest_plate_select")
+                    .selected_text(plates.get(state.nest.plate_idx).map_or(String::new(), |p| p.to_string()))
+                    .show_index(ui, &mut state.nest.plate_idx, plates.len(), |i| plates[i].to_string());
+                ui.label(tr(lang, "nest.spacing"));
+                ui.add(egui::DragValue::new(&mut state.nest.spacing_mm).clamp_range(0.0..=100.0).speed(0.5));
+            });
+            ui.separator();
+
+            match logic::current_nest(state) {
+                Ok(nest) => {
+                    ui.strong(trf(lang, "nest.summary", &[
+                        &nest.plates.len(), &format!("{:.1}", nest.utilisation() * 100.0), &format!("{:.2}", nest.scrap_area_mm2() / 1e6),
+                    ]));
+                    for (i, plate) in nest.plates.iter().enumerate() {
+                        ui.label(trf(lang, "nest.plate_line", &[
+                            &(i + 1), &i18n::material_name(lang, &plate.material_name), &unit.format(plate.thickness_mm, 2),
+                            &plate.placements.len(), &format!("{:.1}", nest.plate_utilisation(plate) * 100.0),
+                        ]));
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(tr(lang, "nest.preview"));
+                        ui.add(egui::DragValue::new(&mut state.nest.preview_plate).clamp_range(1..=nest.plates.len().max(1)));
+                    });
+                    paint_nest_plate(ui, &nest, state.nest.preview_plate.saturating_sub(1));
+                    export = ui.button(tr(lang, "nest.export_dxf")).clicked();
+                }
+                Err(e) => { ui.label(i18n::nest_error(lang, &e)); }
+            }
+        });
+    state.nest.open = open;
+    if add_job {
+        logic::add_job_to_nest(state);
+    }
+    if export {
+        logic::export_nest_dxf(state);
+    }
+}
+
+/// Draws one plate of the nest scaled to the available width, y pointing up.
+fn paint_nest_plate(ui: &mut Ui, nest: &Nest, plate_index: usize) {
+    let Some(plate) = nest.plates.get(plate_index) else { return };
+    let width = ui.available_width().max(100.0);
+    let scale = width / nest.plate.length_mm as f32;
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(width, nest.plate.width_mm as f32 * scale), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    let to_screen = |x: f64, y: f64| Pos2::new(rect.left() + x as f32 * scale, rect.bottom() - y as f32 * scale);
+    painter.rect_stroke(rect, 0.0, Stroke::new(1.0, Color32::GRAY));
+    for placement in &plate.placements {
+        let (length, blank_width) = placement.size_mm(&nest.blanks[placement.blank]);
+        let part = Rect::from_two_pos(to_screen(placement.x_mm, placement.y_mm), to_screen(placement.x_mm + length, placement.y_mm + blank_width));
+        painter.rect(part, 0.0, Color32::from_gray(90), Stroke::new(1.0, Color32::LIGHT_BLUE));
+    }
+}
+
+fn storage_error_dialog(ctx: &Context, state: &mut AppState) {
+    let Some(report) = &state.storage_error else { return };
+    let mut dismissed = false;
+    egui::Window::new(report.action.as_str())
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(RichText::new(report.path.display().to_string()).monospace());
+            ui.add_space(4.0);
+            ui.label(RichText::new(&report.cause).color(Color32::RED));
+            ui.add_space(4.0);
+            dismissed = ui.button(tr(state.language, "common.ok")).clicked();
+        });
+    if dismissed {
+        state.storage_error = None;
+    }
+}
+
+fn file_menu(ui: &mut Ui, state: &mut AppState, ctx: &Context) {
+    let lang = state.language;
+    ui.menu_button(tr(lang, "menu.file"), |ui| {
+        if ui.button(tr(lang, "menu.new")).clicked() {
+            logic::request_job_action(ctx, state, PendingJobAction::NewJob);
+            ui.close_menu();
+        }
+        if ui.button(tr(lang, "menu.open")).clicked() {
+            logic::request_job_action(ctx, state, PendingJobAction::OpenJob);
+            ui.close_menu();
+        }
+        ui.menu_button(tr(lang, "menu.recent"), |ui| {
+            if state.recent_files.is_empty() {
+                ui.label(RichText::new(tr(lang, "menu.recent_empty")).weak());
+            }
+            let mut chosen = None;
+            for path in &state.recent_files {
+                let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+                if ui.button(name).on_hover_text(path.display().to_string()).clicked() {
+                    chosen = Some(path.clone());
+                }
+            }
+            if let Some(path) = chosen {
+                logic::request_job_action(ctx, state, PendingJobAction::OpenFile(path));
+                ui.close_menu();
+            }
+            if !state.recent_files.is_empty() {
+                ui.separator();
+                if ui.button(tr(lang, "menu.recent_clear")).clicked() {
+                    state.recent_files.clear();
+                    ui.close_menu();
+                }
+            }
+        });
+        if ui.button(tr(lang, "menu.library")).clicked() {
+            state.job_library.open = !state.job_library.open;
+            if state.job_library.open && state.job_library.entries.is_empty() {
+                logic::refresh_job_library(state);
+            }
+            ui.close_menu();
+        }
+        ui.separator();
+        if ui.button(tr(lang, "menu.save")).clicked() {
+            logic::save_job(state);
+            ui.close_menu();
+        }
+        if ui.button(tr(lang, "menu.save_as")).clicked() {
+            logic::save_job_as(state);
+            ui.close_menu();
+        }
+        if ui.button(tr(lang, "menu.close")).clicked() {
+            logic::request_job_action(ctx, state, PendingJobAction::CloseJob);
+            ui.close_menu();
+        }
+        ui.separator();
+        if ui.button(tr(lang, "menu.exit")).clicked() {
+            logic::request_job_action(ctx, state, PendingJobAction::Exit);
+            ui.close_menu();
+        }
+    });
+}
+
+fn edit_menu(ui: &mut Ui, state: &mut AppState) {
+    let lang = state.language;
+    ui.menu_button(tr(lang, "menu.edit"), |ui| {
+        let undo = egui::Button::new(tr(lang, "menu.undo")).shortcut_text(ui.ctx().format_shortcut(&UNDO_SHORTCUT));
+        if ui.add_enabled(!state.tab().undo_stack.is_empty(), undo).clicked() {
+            logic::undo(state);
+            ui.close_menu();
+        }
+        let redo = egui::Button::new(tr(lang, "menu.redo")).shortcut_text(ui.ctx().format_shortcut(&REDO_SHORTCUT));
+        if ui.add_enabled(!state.tab().redo_stack.is_empty(), redo).clicked() {
+            logic::redo(state);
+            ui.close_menu();
+        }
+        ui.separator();
+        if ui.button(tr(lang, "menu.copy_steps")).clicked() {
+            logic::copy_bend_steps(state);
             ui.close_menu();
         }
-        if ui.button("Laad Taak Als...").clicked() {
-            logic::handle_save_job(state, Some("jobs/my_output_job.json".to_string()));
+        let paste = egui::Button::new(trf(lang, "menu.paste_steps", &[&state.step_clipboard.len()]));
+        if ui.add_enabled(!state.step_clipboard.is_empty(), paste).clicked() {
+            logic::paste_bend_steps(state);
             ui.close_menu();
         }
-        if ui.button("Exit").clicked() {
-            ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
+    });
+}
+
+/// Undo/redo of job edits. Left to the text field while one has focus, which has its own.
+fn handle_edit_shortcuts(ctx: &Context, state: &mut AppState) {
+    if ctx.memory(|memory| memory.focused().is_some()) {
+        return;
+    }
+    if ctx.input_mut(|input| input.consume_shortcut(&UNDO_SHORTCUT)) {
+        logic::undo(state);
+    }
+    if ctx.input_mut(|input| input.consume_shortcut(&REDO_SHORTCUT)) {
+        logic::redo(state);
+    }
+}
+
+fn job_tab_bar(ui: &mut Ui, state: &mut AppState, ctx: &Context) {
+    let mut switch_to = None;
+    let mut close = None;
+    ui.horizontal_wrapped(|ui| {
+        for (index, tab) in state.tabs.iter().enumerate() {
+            let title = logic::tab_title(tab);
+            let hover = tab.file.as_ref().map_or_else(|| tr(state.language, "app.untitled").to_string(), |path| path.display().to_string());
+            if ui.selectable_label(index == state.active_tab, title).on_hover_text(hover).clicked() {
+                switch_to = Some(index);
+            }
+            if ui.small_button("×").on_hover_text(tr(state.language, "menu.close")).clicked() {
+                close = Some(index);
+            }
+            ui.separator();
+        }
+        if ui.small_button("+").on_hover_text(tr(state.language, "menu.new")).clicked() {
+            logic::request_job_action(ctx, state, PendingJobAction::NewJob);
         }
     });
+    if let Some(index) = close {
+        logic::switch_tab(state, index);
+        logic::request_job_action(ctx, state, PendingJobAction::CloseJob);
+    } else if let Some(index) = switch_to {
+        logic::switch_tab(state, index);
+    }
+}
+
+fn settings_menu(ui: &mut Ui, state: &mut AppState, ctx: &Context) {
+    ui.menu_button(tr(state.language, "menu.settings"), |ui| {
+        language_menu(ui, state);
+        units_menu(ui, state);
+        if ui.button(tr(state.language, "menu.machine_speeds")).clicked() {
+            state.show_machine_speeds = true;
+            ui.close_menu();
+        }
+        ui.separator();
+        if ui.button(tr(state.language, "menu.reset")).clicked() {
+            logic::reset_settings(ctx, state);
+            ui.close_menu();
+        }
+    });
+}
+
+fn language_menu(ui: &mut Ui, state: &mut AppState) {
+    ui.menu_button(tr(state.language, "menu.language"), |ui| {
+        for language in Language::all() {
+            if ui.radio_value(&mut state.language, language, language.to_string()).clicked() {
+                ui.close_menu();
+            }
+        }
+    });
+}
+
+fn units_menu(ui: &mut Ui, state: &mut AppState) {
+    ui.menu_button(tr(state.language, "menu.units"), |ui| {
+        for unit in LengthUnit::all() {
+            if ui.radio(state.length_unit == unit, unit.to_string()).clicked() {
+                logic::set_length_unit(state, unit);
+                ui.close_menu();
+            }
+        }
+    });
+}
+
+fn job_library_panel(ui: &mut Ui, state: &mut AppState, ctx: &Context) {
+    let lang = state.language;
+    ui.heading(tr(lang, "menu.library"));
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, "library.folder"));
+        ui.add(TextEdit::singleline(&mut state.job_library.directory_str).desired_width(140.0));
+        if ui.button(tr(lang, "library.browse")).clicked() {
+            logic::browse_job_library_directory(state);
+        }
+        if ui.button(tr(lang, "library.refresh")).clicked() {
+            logic::refresh_job_library(state);
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr(lang, "library.search"));
+        ui.add(TextEdit::singleline(&mut state.job_library.search).hint_text(tr(lang, "library.search_hint")));
+    });
+    let filter_text = state.job_library.material_filter.as_ref().map_or_else(|| tr(lang, "library.all").to_string(), |m| i18n::material_name(lang, m));
+    ComboBox::from_label(tr(lang, "library.material"))
+        .selected_text(filter_text)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut state.job_library.material_filter, None, tr(lang, "library.all"));
+            for name in &state.material_display_order {
+                ui.selectable_value(&mut state.job_library.material_filter, Some(name.clone()), i18n::material_name(lang, name));
+            }
+        });
+    if state.job_library.unreadable_files > 0 {
+        ui.label(RichText::new(trf(lang, "library.skipped", &[&state.job_library.unreadable_files])).small().weak());
+    }
+    ui.separator();
+
+    let mut to_open = None;
+    ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+        let library = &state.job_library;
+        let visible = library.entries.iter()
+            .filter(|e| logic::job_library_entry_matches(e, &library.search, library.material_filter.as_ref()));
+        for entry in visible {
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    let (rect, _) = ui.allocate_exact_size(Vec2::new(72.0, 54.0), egui::Sense::hover());
+                    ui.painter().rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+                    paint_profile(ui.painter(), rect.shrink(4.0), &entry.profile, Stroke::new(1.5, ui.visuals().text_color()));
+                    ui.vertical(|ui| {
+                        ui.strong(&entry.job.name);
+                        ui.label(trf(lang, "library.summary", &[
+                            &i18n::material_name(lang, &entry.job.sheet.material_name), &state.length_unit.format(entry.job.sheet.thickness_mm, 2), &entry.job.steps.len(),
+                        ]));
+                        ui.label(RichText::new(entry.path.display().to_string()).small().weak());
+                        if ui.button(tr(lang, "library.open")).clicked() {
+                            to_open = Some(entry.path.clone());
+                        }
+                    });
+                });
+            });
+        }
+    });
+    if let Some(path) = to_open {
+        logic::request_job_action(ctx, state, PendingJobAction::OpenFile(path));
+    }
+}
+
+fn unsaved_changes_dialog(ctx: &Context, state: &mut AppState) {
+    if state.pending_job_action.is_none() {
+        return;
+    }
+    let lang = state.language;
+    let mut choice = None;
+    egui::Window::new(tr(lang, "unsaved.title"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+        .show(ctx, |ui| {
+            let dirty_jobs = state.tabs.iter().filter(|tab| tab.dirty).count();
+            if state.pending_job_action == Some(PendingJobAction::Exit) && dirty_jobs > 1 {
+                ui.label(trf(lang, "unsaved.body_many", &[&dirty_jobs]));
+            } else {
+                ui.label(trf(lang, "unsaved.body", &[&state.tab().job.name]));
+            }
+            ui.horizontal(|ui| {
+                if ui.button(tr(lang, "menu.save")).clicked() { choice = Some(UnsavedChangesChoice::Save); }
+                if ui.button(tr(lang, "unsaved.discard")).clicked() { choice = Some(UnsavedChangesChoice::Discard); }
+                if ui.button(tr(lang, "common.cancel")).clicked() { choice = Some(UnsavedChangesChoice::Cancel); }
+            });
+        });
+    if let Some(choice) = choice {
+        logic::resolve_pending_job_action(ctx, state, choice);
+    }
 }
 
 pub fn draw_main_ui(ctx: &Context, state: &mut AppState) {
     egui::TopBottomPanel::top("menu_bar_panel_cnc").show(ctx, |ui| {
         egui::menu::bar(ui, |ui| {
             file_menu(ui, state, ctx);
+            edit_menu(ui, state);
+            settings_menu(ui, state, ctx);
+            ui.toggle_value(&mut state.production.open, tr(state.language, "menu.production"));
+            ui.toggle_value(&mut state.quote.open, tr(state.language, "menu.quote"));
+            ui.toggle_value(&mut state.nest.open, tr(state.language, "menu.nesting"));
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if let (Some(logo_tex), Some(logo_size_val)) = (&state.app_logo_texture, state.app_logo_size) {
                     let desired_height = ui.max_rect().height() * 0.8;
@@ -252,18 +1101,34 @@ pub fn draw_main_ui(ctx: &Context, state: &mut AppState) {
                     let desired_width = desired_height * aspect_ratio;
                     ui.image((logo_tex.id(), Vec2::new(desired_width, desired_height)));
                 }
-                ui.label(RichText::new(format!("CNC Plooibank Sim v0.1 ({})", chrono::Local::now().format("%H:%M:%S"))).small());
+                ui.label(RichText::new(trf(state.language, "app.version", &[&chrono::Local::now().format("%H:%M:%S")])).small());
             });
         });
     });
 
+    egui::TopBottomPanel::top("job_tabs_panel_cnc").show(ctx, |ui| {
+        job_tab_bar(ui, state, ctx);
+    });
+    handle_edit_shortcuts(ctx, state);
+
+    egui::TopBottomPanel::bottom("bottom_status_bar_panel_cnc").show(ctx, |ui| {
+        status_bar(ui, state);
+    });
+
+    if state.show_message_log {
+        egui::TopBottomPanel::bottom("message_log_panel_cnc")
+            .resizable(true)
+            .default_height(140.0)
+            .show(ctx, |ui| message_log_panel(ui, state));
+    }
+
     egui::SidePanel::left("input_controls_panel_cnc")
         .resizable(true)
         .default_width(380.0)
         .width_range(300.0..=500.0)
         .show(ctx, |ui| {
             ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
-                ui.heading("Taak & Machine Setup");
+                ui.heading(tr(state.language, "main.setup"));
                 ui.add_space(10.0);
                 sheet_properties_panel(ui, state);
                 ui.add_space(10.0);
@@ -276,16 +1141,33 @@ pub fn draw_main_ui(ctx: &Context, state: &mut AppState) {
             });
         });
 
+    if state.job_library.open {
+        egui::SidePanel::right("job_library_panel_cnc")
+            .default_width(320.0)
+            .show(ctx, |ui| job_library_panel(ui, state, ctx));
+    }
+
     egui::CentralPanel::default().show(ctx, |ui| {
-        ui.heading("Bediening & Uitvoer");
+        ui.heading(tr(state.language, "main.control"));
         ui.separator();
-        execution_panel(ui, state, ctx);
+        execution_panel(ui, state);
         ui.separator();
         profile_display_panel(ui, state);
     });
 
-    egui::TopBottomPanel::bottom("bottom_status_bar_panel_cnc").show(ctx, |ui| {
-        status_bar(ui, state);
-    });
+    if state.production.open {
+        production_window(ctx, state);
+    }
+    if state.quote.open {
+        quote_window(ctx, state);
+    }
+    if state.nest.open {
+        nest_window(ctx, state);
+    }
+    if state.show_machine_speeds {
+        machine_speeds_window(ctx, state);
+    }
+    unsaved_changes_dialog(ctx, state);
+    storage_error_dialog(ctx, state);
 }
 
@@ -0,0 +1,200 @@
+// src/units.rs
+// Display units. The model stays metric; values are converted at the edges
+// (input fields, labels, reports) only.
+use crate::model::MaterialName;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const MM_PER_INCH: f64 = 25.4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LengthUnit {
+    #[default]
+    Millimeter,
+    Inch,
+}
+
+impl LengthUnit {
+    pub fn all() -> Vec<Self> {
+        vec![LengthUnit::Millimeter, LengthUnit::Inch]
+    }
+
+    /// Accepts the names used on the command line: "mm", "in", "inch".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "mm" | "millimeter" | "millimetre" => Some(LengthUnit::Millimeter),
+            "in" | "inch" | "inches" => Some(LengthUnit::Inch),
+            _ => None,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            LengthUnit::Millimeter => "mm",
+            LengthUnit::Inch => "in",
+        }
+    }
+
+    pub fn from_mm(self, mm: f64) -> f64 {
+        match self {
+            LengthUnit::Millimeter => mm,
+            LengthUnit::Inch => mm / MM_PER_INCH,
+        }
+    }
+
+    pub fn to_mm(self, value: f64) -> f64 {
+        match self {
+            LengthUnit::Millimeter => value,
+            LengthUnit::Inch => value * MM_PER_INCH,
+        }
+    }
+
+    /// Decimals shown in inches for a value shown with `mm_decimals` in mm
+    /// (0.1mm is about 0.004in).
+    fn decimals(self, mm_decimals: usize) -> usize {
+        match self {
+            LengthUnit::Millimeter => mm_decimals,
+            LengthUnit::Inch => mm_decimals + 2,
+        }
+    }
+
+    /// The value without symbol, e.g. "12.70" or "0.5000".
+    pub fn format_value(self, mm: f64, mm_decimals: usize) -> String {
+        format!("{:.*}", self.decimals(mm_decimals), self.from_mm(mm))
+    }
+
+    /// The value with its symbol, e.g. "12.70mm" or "0.5000in".
+    pub fn format(self, mm: f64, mm_decimals: usize) -> String {
+        format!("{}{}", self.format_value(mm, mm_decimals), self.symbol())
+    }
+
+    /// Text for an editable input field: rounded, without trailing zeros.
+    pub fn input_text(self, mm: f64) -> String {
+        let factor = 10f64.powi(self.decimals(3) as i32);
+        ((self.from_mm(mm) * factor).round() / factor).to_string()
+    }
+}
+
+impl fmt::Display for LengthUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LengthUnit::Millimeter => write!(f, "mm"),
+            LengthUnit::Inch => write!(f, "inch"),
+        }
+    }
+}
+
+// --- US Sheet Gauges ---
+
+/// Gauge tables differ per material family; the same number is a different thickness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GaugeStandard {
+    ManufacturersStandard, // Carbon steel
+    StainlessSteel,
+    BrownAndSharpe, // Aluminium and copper (AWG)
+}
+
+impl GaugeStandard {
+    pub fn for_material(material: &MaterialName) -> Self {
+        match material {
+            MaterialName::StainlessSteel => GaugeStandard::StainlessSteel,
+            MaterialName::Aluminum | MaterialName::Copper => GaugeStandard::BrownAndSharpe,
+            MaterialName::Steel | MaterialName::MildSteel | MaterialName::Custom(_) => GaugeStandard::ManufacturersStandard,
+        }
+    }
+
+    /// (gauge, thickness in inches)
+    fn table(self) -> &'static [(u32, f64)] {
+        match self {
+            GaugeStandard::ManufacturersStandard => &[
+                (3, 0.2391), (4, 0.2242), (5, 0.2092), (6, 0.1943), (7, 0.1793), (8, 0.1644), (9, 0.1495),
+                (10, 0.1345), (11, 0.1196), (12, 0.1046), (13, 0.0897), (14, 0.0747), (15, 0.0673), (16, 0.0598),
+                (17, 0.0538), (18, 0.0478), (19, 0.0418), (20, 0.0359), (21, 0.0329), (22, 0.0299), (23, 0.0269),
+                (24, 0.0239), (25, 0.0209), (26, 0.0179), (27, 0.0164), (28, 0.0149), (29, 0.0135), (30, 0.0120),
+            ],
+            GaugeStandard::StainlessSteel => &[
+                (7, 0.1875), (8, 0.1719), (9, 0.1563), (10, 0.1406), (11, 0.1250), (12, 0.1094), (13, 0.0938),
+                (14, 0.0781), (15, 0.0703), (16, 0.0625), (17, 0.0563), (18, 0.0500), (19, 0.0438), (20, 0.0375),
+                (21, 0.0344), (22, 0.0313), (24, 0.0250), (26, 0.0188), (28, 0.0156), (30, 0.0125),
+            ],
+            GaugeStandard::BrownAndSharpe => &[
+                (6, 0.1620), (7, 0.1443), (8, 0.1285), (9, 0.1144), (10, 0.1019), (11, 0.0907), (12, 0.0808),
+                (13, 0.0720), (14, 0.0641), (15, 0.0571), (16, 0.0508), (17, 0.0453), (18, 0.0403), (19, 0.0359),
+                (20, 0.0320), (21, 0.0285), (22, 0.0253), (23, 0.0226), (24, 0.0201), (25, 0.0179), (26, 0.0159),
+            ],
+        }
+    }
+
+    pub fn thickness_mm(self, gauge: u32) -> Option<f64> {
+        self.table().iter().find(|(g, _)| *g == gauge).map(|(_, inch)| inch * MM_PER_INCH)
+    }
+
+    /// Gauge whose thickness is within 2% of `thickness_mm`, for display next to the thickness.
+    pub fn gauge_for_thickness(self, thickness_mm: f64) -> Option<u32> {
+        self.table().iter()
+            .find(|(_, inch)| (inch * MM_PER_INCH - thickness_mm).abs() <= thickness_mm * 0.02)
+            .map(|(g, _)| *g)
+    }
+}
+
+impl fmt::Display for GaugeStandard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GaugeStandard::ManufacturersStandard => write!(f, "Manufacturers' Standard"),
+            GaugeStandard::StainlessSteel => write!(f, "Stainless Steel"),
+            GaugeStandard::BrownAndSharpe => write!(f, "Brown & Sharpe"),
+        }
+    }
+}
+
+/// Gauge number from thickness input such as "16ga", "16 gauge" or "#16".
+pub fn parse_gauge(text: &str) -> Option<u32> {
+    let text = text.trim().to_lowercase();
+    let number = text.strip_prefix('#')
+        .or_else(|| text.strip_suffix("gauge"))
+        .or_else(|| text.strip_suffix("ga"))?;
+    number.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_gauge_notations() {
+        for text in ["16ga", "16 ga", "16 gauge", "16GA", "#16", " # 16 "] {
+            assert_eq!(parse_gauge(text), Some(16), "{}", text);
+        }
+        for text in ["16", "ga", "gauge", "#", "1.5", "sixteen ga"] {
+            assert_eq!(parse_gauge(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn gauge_tables_per_standard() {
+        let close = |standard: GaugeStandard, gauge, mm: f64| {
+            let thickness = standard.thickness_mm(gauge).unwrap();
+            assert!((thickness - mm).abs() < 1e-3, "{} {}ga: {}", standard, gauge, thickness);
+        };
+        close(GaugeStandard::ManufacturersStandard, 16, 1.519);
+        close(GaugeStandard::ManufacturersStandard, 10, 3.416);
+        close(GaugeStandard::StainlessSteel, 16, 1.588);
+        close(GaugeStandard::BrownAndSharpe, 16, 1.290);
+        assert_eq!(GaugeStandard::StainlessSteel.thickness_mm(23), None);
+        assert_eq!(GaugeStandard::ManufacturersStandard.gauge_for_thickness(1.52), Some(16));
+        assert_eq!(GaugeStandard::ManufacturersStandard.gauge_for_thickness(1.6), None);
+    }
+
+    #[test]
+    fn input_text_round_trips() {
+        for mm in [0.0, 1.5, 12.7, 25.4, 147.0, 3000.0] {
+            for unit in LengthUnit::all() {
+                let text = unit.input_text(mm);
+                let back = unit.to_mm(text.parse().unwrap());
+                assert!((back - mm).abs() < 1e-3, "{} {:?}: {} -> {}", mm, unit, text, back);
+            }
+        }
+        assert_eq!(LengthUnit::Inch.input_text(25.4), "1");
+        assert_eq!(LengthUnit::Millimeter.input_text(1.23456), "1.235");
+    }
+}
@@ -0,0 +1,406 @@
+// src/validation.rs
+use crate::geometry::{self, FlangeWarning};
+use crate::handling;
+use crate::model::{BendFeature, BendMethod, BendStep, Job, MachineSetup, MaterialDetails, SheetMetal};
+use crate::physics;
+use std::fmt;
+
+// --- Config Constants ---
+pub const MIN_SHEET_DIMENSION_MM: f64 = 0.1;
+pub const MAX_SHEET_DIMENSION_MM: f64 = 10000.0;
+pub const MIN_BEND_RADIUS_MM: f64 = 0.0; // 0 can mean sharp
+pub const MAX_BEND_RADIUS_MM: f64 = 500.0;
+pub const MIN_BEND_ANGLE_DEG: f64 = 1.0;
+pub const MAX_BEND_ANGLE_DEG: f64 = 179.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SheetDimension {
+    Length,
+    Thickness,
+    Width,
+}
+
+impl fmt::Display for SheetDimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SheetDimension::Length => write!(f, "Length"),
+            SheetDimension::Thickness => write!(f, "Thickness"),
+            SheetDimension::Width => write!(f, "Width"),
+        }
+    }
+}
+
+/// Problems that block a sheet, step or job from being bent.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ValidationError {
+    #[error("{dimension} out of range ({}-{}mm).", MIN_SHEET_DIMENSION_MM, MAX_SHEET_DIMENSION_MM)]
+    SheetDimensionOutOfRange { dimension: SheetDimension, value_mm: f64 },
+    #[error("Bend position ({position_mm}mm) is outside the sheet length (0-{length_mm}mm).")]
+    PositionOutsideSheet { position_mm: f64, length_mm: f64 },
+    #[error("Bend angle ({angle_deg}°) out of range ({}-{}°).", MIN_BEND_ANGLE_DEG, MAX_BEND_ANGLE_DEG)]
+    AngleOutOfRange { angle_deg: f64 },
+    #[error("Bend radius ({radius_mm}mm) out of range ({}-{}mm).", MIN_BEND_RADIUS_MM, MAX_BEND_RADIUS_MM)]
+    RadiusOutOfRange { radius_mm: f64 },
+    #[error("Invalid joggle offset: {offset_mm}mm")]
+    InvalidJoggleOffset { offset_mm: f64 },
+    #[error("Bend line ({position_mm:.1}mm) of this step falls outside the sheet (0-{length_mm}mm).")]
+    HitOutsideSheet { position_mm: f64, length_mm: f64 },
+    #[error("Tools ({punch_angle_deg}°/{die_angle_deg}°) cannot air bend to {target_angle_deg}°.")]
+    ToolsCannotAirBend { punch_angle_deg: f64, die_angle_deg: f64, target_angle_deg: f64 },
+    #[error("{method} needs tools matching {target_angle_deg}° (punch {punch_angle_deg}°, die {die_angle_deg}°).")]
+    ToolAngleMismatch { method: BendMethod, target_angle_deg: f64, punch_angle_deg: f64, die_angle_deg: f64 },
+    #[error("Hemming flattens the flange; target angle must be ≤ {}°.", physics::HEM_FLATTEN_MAX_ANGLE_DEG)]
+    HemNotFlattened { target_angle_deg: f64 },
+    #[error("Hemming at {position_mm}mm needs a pre-bend step to ≤ {}° at the same position.", physics::HEM_PREBEND_MAX_ANGLE_DEG)]
+    MissingHemPrebend { position_mm: f64 },
+    #[error("{method} needs ≈ {force_kn:.0}kN, press capacity is {:.0}kN.", physics::PRESS_CAPACITY_KN)]
+    ForceExceedsCapacity { method: BendMethod, force_kn: f64 },
+    #[error("No bend steps to simulate.")]
+    NoBendSteps,
+    #[error("Step {sequence_order}: {source}")]
+    InStep { sequence_order: usize, source: Box<ValidationError> },
+}
+
+/// Problems worth telling the operator about that don't block bending.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationWarning {
+    RadiusBelowMaterialMinimum { radius_mm: f64, min_radius_mm: f64 },
+    RadiusMismatch { sequence_order: usize, method: BendMethod, punch: String, die: String, formed_radius_mm: f64, requested_radius_mm: f64 },
+    ShortFlanges(Vec<FlangeWarning>),
+    ConvertedToBump { radius_mm: f64, feature: BendFeature },
+    HeavyPart { mass_kg: f64, limit_kg: f64 },
+    BendAlongRollingDirection { sequence_order: usize, radius_mm: f64, min_radius_mm: f64 },
+    TipsAwayFromBackgauge { sequence_orders: Vec<usize> },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationWarning::RadiusBelowMaterialMinimum { radius_mm, min_radius_mm } =>
+                write!(f, "Warning: Radius {:.2}mm < recommended min {:.2}mm for material.", radius_mm, min_radius_mm),
+            ValidationWarning::RadiusMismatch { sequence_order, method, punch, die, formed_radius_mm, requested_radius_mm } =>
+                write!(f, "Warning: Step {}: {} with {} / {} forms ≈ {:.2}mm radius, not {:.2}mm.", sequence_order, method, punch, die, formed_radius_mm, requested_radius_mm),
+            ValidationWarning::ShortFlanges(warnings) => {
+                let shortest = warnings.iter().map(|w| w.flange_mm).fold(f64::INFINITY, f64::min);
+                let orders: Vec<String> = warnings.iter().map(|w| w.sequence_order.to_string()).collect();
+                write!(
+                    f,
+                    "Warning: flange {:.1}mm too short for selected die (min {:.1}mm) at step(s) {}.",
+                    shortest, warnings.first().map_or(0.0, |w| w.min_flange_mm), orders.join(", ")
+                )
+            }
+            ValidationWarning::ConvertedToBump { radius_mm, feature } =>
+                write!(f, "Radius {:.1}mm too large for a single hit; converted to {}.", radius_mm, feature),
+            ValidationWarning::BendAlongRollingDirection { sequence_order, radius_mm, min_radius_mm } =>
+                write!(f, "Warning: Step {}: bend line along the rolling direction, radius {:.2}mm < min {:.2}mm; risk of cracking.", sequence_order, radius_mm, min_radius_mm),
+            ValidationWarning::HeavyPart { mass_kg, limit_kg } =>
+                write!(f, "Warning: part weighs {:.1}kg, more than one operator should handle ({:.0}kg).", mass_kg, limit_kg),
+            ValidationWarning::TipsAwayFromBackgauge { sequence_orders } => {
+                let orders: Vec<String> = sequence_orders.iter().map(|o| o.to_string()).collect();
+                write!(f, "Warning: part tips away from the backgauge at step(s) {}; support it while bending.", orders.join(", "))
+            }
+        }
+    }
+}
+
+// --- Sheet and Step Input ---
+
+pub fn validate_sheet_dimensions(sheet: &SheetMetal) -> Result<(), ValidationError> {
+    for (dimension, value_mm) in [
+        (SheetDimension::Length, sheet.original_length_mm),
+        (SheetDimension::Thickness, sheet.thickness_mm),
+        (SheetDimension::Width, sheet.width_mm),
+    ] {
+        if !(MIN_SHEET_DIMENSION_MM..=MAX_SHEET_DIMENSION_MM).contains(&value_mm) {
+            return Err(ValidationError::SheetDimensionOutOfRange { dimension, value_mm });
+        }
+    }
+    Ok(())
+}
+
+/// Range checks on the numbers an operator enters for a step.
+pub fn validate_step_values(step: &BendStep, sheet: &SheetMetal) -> Result<(), ValidationError> {
+    if step.position_mm <= 0.0 || step.position_mm >= sheet.original_length_mm {
+        return Err(ValidationError::PositionOutsideSheet { position_mm: step.position_mm, length_mm: sheet.original_length_mm });
+    }
+    if !(MIN_BEND_ANGLE_DEG..=MAX_BEND_ANGLE_DEG).contains(&step.target_angle_deg) {
+        return Err(ValidationError::AngleOutOfRange { angle_deg: step.target_angle_deg });
+    }
+    if !(MIN_BEND_RADIUS_MM..=MAX_BEND_RADIUS_MM).contains(&step.radius_mm) {
+        return Err(ValidationError::RadiusOutOfRange { radius_mm: step.radius_mm });
+    }
+    if let BendFeature::Joggle { offset_mm } = step.feature
+        && offset_mm <= 0.0
+    {
+        return Err(ValidationError::InvalidJoggleOffset { offset_mm });
+    }
+    Ok(())
+}
+
+// --- Bending Method Validation ---
+
+/// Checks a machine hit against the physics of its bending method and the tools
+/// in the setup. `Err` blocks the hit; `Ok` may still carry warnings.
+pub fn validate_hit(setup: &MachineSetup, step: &BendStep, previous_steps: &[BendStep]) -> Result<Vec<ValidationWarning>, ValidationError> {
+    let MachineSetup { sheet, material, punch, die } = *setup;
+    let mut warnings = Vec::new();
+
+    match step.method {
+        BendMethod::AirBending => {
+            if punch.angle_deg > step.target_angle_deg || die.angle_deg > step.target_angle_deg {
+                return Err(ValidationError::ToolsCannotAirBend {
+                    punch_angle_deg: punch.angle_deg,
+                    die_angle_deg: die.angle_deg,
+                    target_angle_deg: step.target_angle_deg,
+                });
+            }
+        }
+        BendMethod::Bottoming | BendMethod::Coining => {
+            let die_mismatch = (die.angle_deg - step.target_angle_deg).abs();
+            let punch_mismatch = (punch.angle_deg - step.target_angle_deg).abs();
+            if die_mismatch > physics::BOTTOMING_ANGLE_TOLERANCE_DEG || punch_mismatch > physics::BOTTOMING_ANGLE_TOLERANCE_DEG {
+                return Err(ValidationError::ToolAngleMismatch {
+                    method: step.method,
+                    target_angle_deg: step.target_angle_deg,
+                    punch_angle_deg: punch.angle_deg,
+                    die_angle_deg: die.angle_deg,
+                });
+            }
+        }
+        BendMethod::Hemming => {
+            if step.target_angle_deg > physics::HEM_FLATTEN_MAX_ANGLE_DEG {
+                return Err(ValidationError::HemNotFlattened { target_angle_deg: step.target_angle_deg });
+            }
+            let has_prebend = previous_steps.iter().any(|s| {
+                (s.position_mm - step.position_mm).abs() < 1e-6
+                    && s.method != BendMethod::Hemming
+                    && s.target_angle_deg <= physics::HEM_PREBEND_MAX_ANGLE_DEG
+            });
+            if !has_prebend {
+                return Err(ValidationError::MissingHemPrebend { position_mm: step.position_mm });
+            }
+        }
+    }
+
+    let formed_radius = physics::formed_inner_radius_mm(step.method, punch, die, material);
+    if step.method != BendMethod::Hemming && (formed_radius - step.radius_mm).abs() > sheet.thickness_mm * 0.5 {
+        warnings.push(ValidationWarning::RadiusMismatch {
+            sequence_order: step.sequence_order,
+            method: step.method,
+            punch: punch.name.clone(),
+            die: die.name.clone(),
+            formed_radius_mm: formed_radius,
+            requested_radius_mm: step.radius_mm,
+        });
+    }
+
+    let force_kn = physics::bend_force_kn(sheet, material, die, step.method);
+    if force_kn > physics::PRESS_CAPACITY_KN {
+        return Err(ValidationError::ForceExceedsCapacity { method: step.method, force_kn });
+    }
+    Ok(warnings)
+}
+
+fn in_step(sequence_order: usize, error: ValidationError) -> ValidationError {
+    ValidationError::InStep { sequence_order, source: Box::new(error) }
+}
+
+/// Validates the sheet, the entered step values and all machine hits of a job
+/// against the tools in the setup.
+pub fn validate_job(setup: &MachineSetup, job: &Job) -> Result<Vec<ValidationWarning>, ValidationError> {
+    validate_sheet_dimensions(&job.sheet)?;
+    if job.steps.is_empty() {
+        return Err(ValidationError::NoBendSteps);
+    }
+    for step in &job.steps {
+        validate_step_values(step, &job.sheet).map_err(|e| in_step(step.sequence_order, e))?;
+    }
+    let length = job.sheet.original_length_mm;
+    let hits = geometry::expand_machine_steps(job);
+    let mut warnings = Vec::new();
+    for (i, hit) in hits.iter().enumerate() {
+        if hit.position_mm <= 0.0 || hit.position_mm >= length {
+            let outside = ValidationError::HitOutsideSheet { position_mm: hit.position_mm, length_mm: length };
+            return Err(in_step(hit.sequence_order, outside));
+        }
+        warnings.extend(validate_hit(setup, hit, &hits[..i]).map_err(|e| in_step(hit.sequence_order, e))?);
+    }
+    let flange_warnings = geometry::check_min_flange_lengths(job, setup.die);
+    if !flange_warnings.is_empty() {
+        warnings.push(ValidationWarning::ShortFlanges(flange_warnings));
+    }
+    warnings.extend(rolling_direction_warnings(setup.material, job));
+    let part = handling::analyse(setup, job);
+    if part.over_handling_limit() {
+        warnings.push(ValidationWarning::HeavyPart { mass_kg: part.mass_kg, limit_kg: handling::SINGLE_OPERATOR_LIMIT_KG });
+    }
+    let tipping: Vec<usize> = part.steps.iter().filter(|s| s.tips_away).map(|s| s.sequence_order).collect();
+    if !tipping.is_empty() {
+        warnings.push(ValidationWarning::TipsAwayFromBackgauge { sequence_orders: tipping });
+    }
+    Ok(warnings)
+}
+
+// --- Adding Steps ---
+
+/// Bump plan for the step with the tools in the setup, if its radius is too large
+/// for a single air bend hit (or bump bending was requested explicitly).
+pub fn bump_feature_for(setup: &MachineSetup, step: &BendStep, requested: bool) -> Option<BendFeature> {
+    let too_large = step.radius_mm > physics::max_single_hit_radius_mm(setup.punch, setup.die, setup.material);
+    let automatic = too_large && step.method == BendMethod::AirBending && step.feature == BendFeature::Single;
+    if !requested && !automatic {
+        return None;
+    }
+    Some(bump_plan(setup, step))
+}
+
+/// Bump plan for the step's radius with the tools in the setup, each hit air bent.
+pub fn bump_plan(setup: &MachineSetup, step: &BendStep) -> BendFeature {
+    let hit_radius = physics::formed_inner_radius_mm(BendMethod::AirBending, setup.punch, setup.die, setup.material);
+    geometry::plan_bump_bending(step, setup.sheet.thickness_mm, setup.die.v_opening_mm, hit_radius)
+}
+
+/// Radius below what the material takes at the step's bend line orientation.
+/// A radius of 0 (sharp bends, coining) is left alone across the grain, but
+/// along the rolling direction it is the bend most likely to crack.
+pub fn material_radius_warning(material: &MaterialDetails, sheet: &SheetMetal, step: &BendStep) -> Option<ValidationWarning> {
+    let min_radius = material.min_bend_radius_mm(sheet.thickness_mm, sheet.rolling_direction);
+    let parallel = sheet.rolling_direction.bend_lines_parallel();
+    if step.radius_mm >= min_radius || (step.radius_mm <= 1e-6 && !parallel) {
+        return None;
+    }
+    Some(if parallel {
+        ValidationWarning::BendAlongRollingDirection { sequence_order: step.sequence_order, radius_mm: step.radius_mm, min_radius_mm: min_radius }
+    } else {
+        ValidationWarning::RadiusBelowMaterialMinimum { radius_mm: step.radius_mm, min_radius_mm: min_radius }
+    })
+}
+
+/// Steps bent along the rolling direction tighter than the material allows,
+/// e.g. after the rolling direction of the sheet was changed.
+pub fn rolling_direction_warnings(material: &MaterialDetails, job: &Job) -> Vec<ValidationWarning> {
+    if !job.sheet.rolling_direction.bend_lines_parallel() {
+        return Vec::new();
+    }
+    job.steps.iter().filter_map(|step| material_radius_warning(material, &job.sheet, step)).collect()
+}
+
+/// A step ready to be appended to a job, with what the operator should know about it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedStep {
+    pub step: BendStep,
+    pub warnings: Vec<ValidationWarning>,
+}
+
+/// Validates a new step against the job it will be appended to, turning it into
+/// a bump bend when its radius needs one.
+pub fn prepare_step(setup: &MachineSetup, job: &Job, mut step: BendStep, bump_requested: bool) -> Result<PreparedStep, ValidationError> {
+    validate_step_values(&step, &job.sheet)?;
+    let mut warnings = Vec::new();
+
+    if let Some(warning) = material_radius_warning(setup.material, &job.sheet, &step) {
+        warnings.push(warning);
+    }
+
+    if let Some(bump) = bump_feature_for(setup, &step, bump_requested) {
+        if !bump_requested {
+            warnings.push(ValidationWarning::ConvertedToBump { radius_mm: step.radius_mm, feature: bump.clone() });
+        }
+        step.feature = bump;
+    }
+
+    let length = job.sheet.original_length_mm;
+    let new_hits = geometry::expand_feature(&step, job.sheet.thickness_mm);
+    if let Some(outside) = new_hits.iter().find(|hit| hit.position_mm <= 0.0 || hit.position_mm >= length) {
+        return Err(ValidationError::HitOutsideSheet { position_mm: outside.position_mm, length_mm: length });
+    }
+
+    let mut hits_so_far = geometry::expand_machine_steps(job);
+    for hit in new_hits {
+        warnings.extend(validate_hit(setup, &hit, &hits_so_far)?);
+        hits_so_far.push(hit);
+    }
+
+    let mut extended = job.clone();
+    extended.steps.push(step.clone());
+    let flange_warnings = geometry::check_min_flange_lengths(&extended, setup.die);
+    if !flange_warnings.is_empty() {
+        warnings.push(ValidationWarning::ShortFlanges(flange_warnings));
+    }
+    Ok(PreparedStep { step, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{self, BendDirection, MaterialName, RollingDirection};
+
+    fn step(radius_mm: f64) -> BendStep {
+        BendStep {
+            sequence_order: 1,
+            position_mm: 100.0,
+            target_angle_deg: 90.0,
+            radius_mm,
+            direction: BendDirection::Up,
+            method: BendMethod::AirBending,
+            feature: BendFeature::Single,
+        }
+    }
+
+    #[test]
+    fn sharp_bend_warns_only_along_the_rolling_direction() {
+        let materials = model::default_materials();
+        let steel = &materials[&MaterialName::Steel];
+        let mut sheet = SheetMetal { rolling_direction: RollingDirection::AlongLength, ..SheetMetal::default() };
+        assert_eq!(material_radius_warning(steel, &sheet, &step(0.0)), None);
+
+        sheet.rolling_direction = RollingDirection::AlongWidth;
+        assert!(matches!(
+            material_radius_warning(steel, &sheet, &step(0.0)),
+            Some(ValidationWarning::BendAlongRollingDirection { sequence_order: 1, .. })
+        ));
+        let job = Job { sheet, steps: vec![step(0.0)], ..Job::default() };
+        assert_eq!(rolling_direction_warnings(steel, &job).len(), 1);
+    }
+
+    #[test]
+    fn parallel_bends_need_the_larger_radius() {
+        let materials = model::default_materials();
+        let steel = &materials[&MaterialName::Steel];
+        let across = SheetMetal { thickness_mm: 2.0, rolling_direction: RollingDirection::AlongLength, ..SheetMetal::default() };
+        let along = SheetMetal { rolling_direction: RollingDirection::AlongWidth, ..across.clone() };
+        // Steel: 1.5 t across, 2.5 t along the grain
+        assert_eq!(material_radius_warning(steel, &across, &step(4.0)), None);
+        assert!(matches!(
+            material_radius_warning(steel, &across, &step(2.0)),
+            Some(ValidationWarning::RadiusBelowMaterialMinimum { .. })
+        ));
+        match material_radius_warning(steel, &along, &step(4.0)) {
+            Some(ValidationWarning::BendAlongRollingDirection { min_radius_mm, .. }) => assert!((min_radius_mm - 5.0).abs() < 1e-9),
+            other => panic!("expected a rolling direction warning, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn simulate_rejects_out_of_range_steps() {
+        use crate::simulation;
+        let materials = model::default_materials();
+        let (punches, dies) = (model::default_punches(), model::default_dies());
+        let sheet = SheetMetal::default();
+        let setup = MachineSetup { sheet: &sheet, material: &materials[&MaterialName::Steel], punch: &punches[0], die: &dies[0] };
+
+        let outside = BendStep { position_mm: sheet.original_length_mm + 10.0, ..step(2.0) };
+        let job = Job { sheet: sheet.clone(), steps: vec![outside], ..Job::default() };
+        assert!(matches!(
+            simulation::simulate(&setup, &job),
+            Err(ValidationError::InStep { sequence_order: 1, source }) if matches!(*source, ValidationError::PositionOutsideSheet { .. })
+        ));
+
+        let too_sharp = BendStep { target_angle_deg: 185.0, ..step(2.0) };
+        let job = Job { sheet: sheet.clone(), steps: vec![too_sharp], ..Job::default() };
+        assert!(matches!(
+            simulation::simulate(&setup, &job),
+            Err(ValidationError::InStep { sequence_order: 1, source }) if matches!(*source, ValidationError::AngleOutOfRange { .. })
+        ));
+    }
+}